@@ -1,83 +1,324 @@
+use common::{dxf, svg};
 use rsolid::*;
 
-const BASE_D: f64 = 62.0; // 72.0;
-const BASE_R: f64 = BASE_D / 2.0;
-const UPPER_D: f64 = 92.0; // 95.0;
-const UPPER_R: f64 = UPPER_D / 2.0;
 const BASE_H: f64 = 2.5;
 const DISH_H: f64 = 2.5;
 const SCREW_D: f64 = 5.0;
-const BRACKET_H: f64 = 37.0 - BASE_H;
-
-fn hole() -> Object<2> {
-    let s = square([36.0, 14.85]).center(true);
-    let c = circle(35.6 / 2.0);
-    let s2 = square([17.15, 37.75]).center(true);
-    let s3 = square([8.25, 47.5]).center(true);
-    let notch = square([4.75, 2.3]).center(true);
-    let notch_spacing = 52.0 / 2.0;
-    let s4 = notch >> fwd(notch_spacing);
-    let s5 = notch >> back(notch_spacing);
-
-    s + c + s2 + s3 + s4 + s5
-}
+/// How far the screw holes sit in from the rim
+const SCREW_INSET: f64 = 4.0;
+/// How far below the rim each screw hole needs material all the way round
+const SCREW_GRIP: f64 = 3.0;
+/// The nut slot across each screw hole, centered at mid-height and open through the dish wall
+const INSERT_D: f64 = SCREW_D + 1.9;
+const INSERT_H: f64 = 12.0;
+const INSERT_INSET: f64 = 5.0;
+const SUPPORT: [f64; 3] = [13.0, 41.0, 50.0];
+/// The least material left around a hole
+const MIN_WALL: f64 = 0.8;
 
-fn bottom() -> Object {
-    let surface = circle(BASE_R) - hole();
-    surface >> linear_extrude(BASE_H) >> down(BRACKET_H / 2.0 + BASE_H)
+/// The can an attachment drops into
+#[derive(Clone, Copy, Debug)]
+struct Can {
+    name: &'static str,
+    /// The inside of the rim, which the top of the dish fits
+    d: f64,
+    /// The lip the attachment's base rests on
+    lip_d: f64,
+    /// How far below the rim the lip is
+    lip_depth: f64,
 }
 
-fn outer_dish() -> Object {
-    cone(BRACKET_H, BASE_R, UPPER_R).center(true).into()
-}
+const CAN_92: Can = Can {
+    name: "can_92",
+    d: 92.0,
+    lip_d: 62.0,
+    lip_depth: 37.0,
+};
 
-fn dish() -> Object {
-    let c = outer_dish();
-    let b = cone(BRACKET_H + 1.0, BASE_R - DISH_H, UPPER_R - DISH_H).center(true);
+const CAN_95: Can = Can {
+    name: "can_95",
+    d: 95.0,
+    lip_d: 72.0,
+    lip_depth: 37.0,
+};
 
-    c - b
+/// The rectangles of the cross profile, as their size and how far back their center is
+const CROSS_PARTS: [([f64; 2], f64); 5] = [
+    ([36.0, 14.85], 0.0),
+    ([17.15, 37.75], 0.0),
+    ([8.25, 47.5], 0.0),
+    ([4.75, 2.3], 26.0),
+    ([4.75, 2.3], -26.0),
+];
+const CROSS_D: f64 = 35.6;
+
+/// The hole the accessory passes through the base
+#[derive(Clone, Copy, Debug)]
+enum Profile {
+    /// A round body with flats and locating notches front and back
+    Cross,
+    Round {
+        d: f64,
+    },
+    /// Artwork in `src/`, scaled to `width`
+    Svg {
+        file: &'static str,
+        width: f64,
+    },
+    /// A CAD profile in `src/`, drawn to size
+    Dxf {
+        file: &'static str,
+    },
 }
 
-fn cutout() -> Object {
-    let side = &cube([UPPER_D + 5.0, 100.0, 100.0]).center(true);
-    let shift = BASE_D - 2.0;
-    let a = side.fwd(shift);
-    let b = side.back(shift);
+impl Profile {
+    fn svg(file: &str, width: f64) -> svg::Svg {
+        svg::svg(format!("{}/src/{file}", env!("CARGO_MANIFEST_DIR")))
+            .trim()
+            .width(width)
+            .center()
+    }
 
-    a + b
-}
+    fn dxf(file: &str) -> dxf::Dxf {
+        dxf::dxf(format!("{}/src/{file}", env!("CARGO_MANIFEST_DIR"))).center()
+    }
 
-fn screw_holes() -> Object {
-    let hole = &cylinder(100, SCREW_D / 2.0).center(true).up(BRACKET_H);
-    let distance = 4.0;
-    let a = hole.left(UPPER_R - distance);
-    let b = hole.right(UPPER_R - distance);
+    fn shape(&self) -> Object<2> {
+        match *self {
+            Self::Cross => {
+                let mut shape = circle(CROSS_D / 2.0).into_object();
+                for (size, y) in CROSS_PARTS {
+                    shape += square(size).center(true) >> back(y);
+                }
+                shape
+            }
+            Self::Round { d } => circle(d / 2.0).into(),
+            Self::Svg { file, width } => Self::svg(file, width).into_object(),
+            Self::Dxf { file } => Self::dxf(file).into_object(),
+        }
+    }
 
-    a + b
+    /// How far the hole reaches from the middle of the base
+    fn reach(&self) -> f64 {
+        match *self {
+            Self::Cross => CROSS_PARTS
+                .iter()
+                .map(|([w, h], y)| (w / 2.0).hypot(y.abs() + h / 2.0))
+                .fold(CROSS_D / 2.0, f64::max),
+            Self::Round { d } => d / 2.0,
+            Self::Svg { file, width } => {
+                let [w, h] = Self::svg(file, width).fitted_size();
+                (w / 2.0).hypot(h / 2.0)
+            }
+            Self::Dxf { file } => Self::dxf(file)
+                .contours()
+                .iter()
+                .flatten()
+                .map(|[x, y]| x.hypot(*y))
+                .fold(0.0, f64::max),
+        }
+    }
 }
 
-fn screw_thing() -> Object {
-    let hole = &cylinder(12, SCREW_D / 2.0 + 0.95).center(true);
-    let distance = 5.0;
-    let a = hole.left(UPPER_R - distance);
-    let b = hole.right(UPPER_R - distance);
-
-    a + b
+#[derive(Clone, Copy, Debug)]
+struct Attachment {
+    name: &'static str,
+    can: Can,
+    profile: Profile,
 }
 
-fn screw_support() -> Object {
-    let height = 50.0;
-    let support = &cube([13.0, 41.0, height]).center(true).up(BRACKET_H - 10.0);
+impl Attachment {
+    fn base_r(&self) -> f64 {
+        self.can.lip_d / 2.0
+    }
+
+    fn upper_r(&self) -> f64 {
+        self.can.d / 2.0
+    }
+
+    fn bracket_h(&self) -> f64 {
+        self.can.lip_depth - BASE_H
+    }
+
+    /// The outside of the dish's radius at `z`, measured from the middle of the bracket
+    fn dish_r(&self, z: f64) -> f64 {
+        let t = z / self.bracket_h() + 0.5;
+        self.base_r() + (self.upper_r() - self.base_r()) * t
+    }
+
+    /// How far either side of the middle the cutout leaves the dish standing
+    fn band(&self) -> f64 {
+        self.can.lip_d - 2.0 - 50.0
+    }
+
+    fn validate(&self) {
+        let name = self.name;
+        let can = self.can.name;
+
+        let reach = self.profile.reach();
+        assert!(
+            reach + MIN_WALL <= self.base_r(),
+            "{name}: the accessory hole reaches {reach:.1}mm out but the {can} base is only {:.1}mm",
+            self.base_r()
+        );
+
+        // the screw holes are upright but the dish flares out, so only their top is enclosed
+        let edge = self.upper_r() - SCREW_INSET + SCREW_D / 2.0;
+        let flare = (self.upper_r() - self.base_r()) / self.bracket_h();
+        let grip = if flare > 0.0 {
+            (self.upper_r() - edge) / flare
+        } else {
+            f64::INFINITY
+        };
+        assert!(
+            grip >= SCREW_GRIP,
+            "{name}: the screw holes only have {grip:.1}mm of the {can} dish wall around them"
+        );
+
+        let insert = [
+            self.upper_r() - INSERT_INSET - INSERT_D / 2.0,
+            self.upper_r() - INSERT_INSET + INSERT_D / 2.0,
+        ];
+        let support = [
+            self.upper_r() - SCREW_INSET - SUPPORT[0] / 2.0,
+            self.upper_r() - SCREW_INSET + SUPPORT[0] / 2.0,
+        ];
+        assert!(
+            insert[0] >= support[0] + MIN_WALL && insert[1] <= support[1],
+            "{name}: the screw inserts don't land in the screw supports"
+        );
+
+        // the nut slot breaks out through the flared dish wall so a nut slides in from outside,
+        // which needs it open up to its top, where the wall is furthest out
+        let wall = self.dish_r(INSERT_H / 2.0);
+        assert!(
+            insert[1] >= wall,
+            "{name}: the nut slots stop {:.1}mm inside the {can} dish wall, so nuts can't go in",
+            wall - insert[1]
+        );
+
+        let screw = [
+            self.upper_r() - SCREW_INSET - SCREW_D / 2.0,
+            self.upper_r() - SCREW_INSET + SCREW_D / 2.0,
+        ];
+        assert!(
+            screw[0] >= insert[0] && screw[1] <= insert[1],
+            "{name}: the screw holes don't pass through the nut slots"
+        );
+        assert!(
+            self.band() >= INSERT_D / 2.0 + MIN_WALL,
+            "{name}: the {can} cutout leaves only {:.1}mm of dish either side of the screws",
+            self.band()
+        );
+    }
 
-    let a = support.left(UPPER_R - 4.0);
-    let b = support.right(UPPER_R - 4.0);
+    fn bottom(&self) -> Object {
+        let surface = circle(self.base_r()) - self.profile.shape();
+        surface >> linear_extrude(BASE_H) >> down(self.bracket_h() / 2.0 + BASE_H)
+    }
 
-    (a + b) & outer_dish()
+    fn outer_dish(&self) -> Object {
+        cone(self.bracket_h(), self.base_r(), self.upper_r())
+            .center(true)
+            .into()
+    }
+
+    fn dish(&self) -> Object {
+        let c = self.outer_dish();
+        let b = cone(
+            self.bracket_h() + 1.0,
+            self.base_r() - DISH_H,
+            self.upper_r() - DISH_H,
+        )
+        .center(true);
+
+        c - b
+    }
+
+    fn cutout(&self) -> Object {
+        let side = &cube([self.can.d + 5.0, 100.0, 100.0]).center(true);
+        let shift = self.can.lip_d - 2.0;
+        let a = side.fwd(shift);
+        let b = side.back(shift);
+
+        a + b
+    }
+
+    fn screw_holes(&self) -> Object {
+        let hole = &cylinder(100, SCREW_D / 2.0)
+            .center(true)
+            .up(self.bracket_h());
+        let a = hole.left(self.upper_r() - SCREW_INSET);
+        let b = hole.right(self.upper_r() - SCREW_INSET);
+
+        a + b
+    }
+
+    fn screw_thing(&self) -> Object {
+        let hole = &cylinder(INSERT_H, INSERT_D / 2.0).center(true);
+        let a = hole.left(self.upper_r() - INSERT_INSET);
+        let b = hole.right(self.upper_r() - INSERT_INSET);
+
+        a + b
+    }
+
+    fn screw_support(&self) -> Object {
+        let support = &cube(SUPPORT).center(true).up(self.bracket_h() - 10.0);
+
+        let a = support.left(self.upper_r() - SCREW_INSET);
+        let b = support.right(self.upper_r() - SCREW_INSET);
+
+        (a + b) & self.outer_dish()
+    }
+
+    fn model(&self) -> Object {
+        self.validate();
+
+        self.dish() + self.screw_support() - self.cutout() + self.bottom()
+            - self.screw_holes()
+            - self.screw_thing()
+    }
 }
 
 fn main() {
-    let h = dish() + screw_support() - cutout() + bottom() - screw_holes() - screw_thing();
-    let out = h >> fragment_count(200).preview(50);
+    let attachments = [
+        Attachment {
+            name: "can_attachment",
+            can: CAN_92,
+            profile: Profile::Cross,
+        },
+        Attachment {
+            name: "can_attachment_95",
+            can: CAN_95,
+            profile: Profile::Cross,
+        },
+        Attachment {
+            name: "can_attachment_round",
+            can: CAN_92,
+            profile: Profile::Round { d: 40.0 },
+        },
+        Attachment {
+            name: "can_attachment_pump",
+            can: CAN_95,
+            profile: Profile::Svg {
+                file: "pump.svg",
+                width: 42.0,
+            },
+        },
+        Attachment {
+            name: "can_attachment_nozzle",
+            can: CAN_92,
+            profile: Profile::Dxf { file: "nozzle.dxf" },
+        },
+    ];
+
+    for attachment in &attachments {
+        let out = attachment.model() >> fragment_count(200).preview(50);
+
+        rsolid::export!(out, attachment.name, &["3mf"]);
 
-    rsolid::export!(out, &["3mf"]);
+        // the hole profile on its own, for cutting the same opening in a lid or plate
+        let hole = attachment.profile.shape();
+        rsolid::export!(hole, format!("{}_hole", attachment.name), &["dxf"]);
+    }
 }
@@ -1,36 +1,518 @@
+use common::{
+    printer,
+    split::{self, Joint, Split},
+    svg, text,
+};
 use rsolid::*;
 
-const INNER_R: f64 = 55.0;
-const OUTER_R: f64 = 90.0;
-const BOTTOM_CUTOUT_R: f64 = 7.68;
+/// The face radius every other proportion was designed at
+const REFERENCE_R: f64 = 90.0;
+const INNER_RATIO: f64 = 55.0 / REFERENCE_R;
+const MOUNT_RATIOS: [f64; 2] = [61.0 / REFERENCE_R, 84.0 / REFERENCE_R];
+const CENTER_RIM_RATIO: f64 = 73.5 / REFERENCE_R;
+
+const RIM_H: f64 = 5.0;
+const RIM_T: f64 = 2.0;
+const MOUNT_HOLE_R: f64 = 2.0;
+const FACE_T: f64 = 1.0;
+/// The dial hangs below the face on its printed screws
+const DIAL_Z: [f64; 2] = [-3.0, -1.0];
+
+const HAND_T: f64 = 1.2;
+const HAND_HUB_WALL: f64 = 1.6;
+/// Space left between the minute hand's tip and the numerals
+const HAND_MARGIN: f64 = 1.5;
+
+#[derive(Clone, Copy, Debug)]
+struct Post {
+    base_r: f64,
+    base_h: f64,
+    pin_r: f64,
+    pin_h: f64,
+}
+
+/// The hand shafts a movement drives, as diameters
+#[derive(Clone, Copy, Debug)]
+struct Shafts {
+    hour: f64,
+    /// The diameter and the width across its flats
+    minute: [f64; 2],
+    second: Option<f64>,
+}
+
+/// A movement's footprint, measured in its own frame
+#[derive(Clone, Copy, Debug)]
+struct Movement {
+    name: &'static str,
+    /// Where the hand shaft comes through
+    shaft: [f64; 2],
+    /// Where the posts holding the face to the movement go
+    posts: &'static [[f64; 2]],
+    post: Post,
+    shafts: Shafts,
+    /// Openings through the face, as `(center, radius)`, e.g. for an escapement arbor
+    cutouts: &'static [([f64; 2], f64)],
+}
+
+/// The printed pendulum skeleton in `clock-skeleton`
+const SKELETON: Movement = Movement {
+    name: "skeleton",
+    shaft: [0.0, 0.0],
+    posts: &[[60.0, 40.7], [-60.0, 40.7], [60.0, -40.7], [-60.0, -40.7]],
+    post: Post {
+        base_r: 6.0,
+        base_h: 13.0,
+        pin_r: 4.0,
+        pin_h: 18.5,
+    },
+    // printed arbors
+    shafts: Shafts {
+        hour: 8.0,
+        minute: [5.0, 3.5],
+        second: None,
+    },
+    cutouts: &[([0.0, 52.82], 7.68)],
+};
+
+const QUARTZ_SHAFTS: Shafts = Shafts {
+    hour: 5.0,
+    minute: [3.7, 2.5],
+    second: Some(1.0),
+};
+
+/// A standard 56mm square quartz movement, measured from the corner of its case
+const QUARTZ: Movement = Movement {
+    name: "quartz",
+    shaft: [28.0, 28.0],
+    posts: &[[-8.0, -8.0], [64.0, -8.0], [-8.0, 64.0], [64.0, 64.0]],
+    post: Post {
+        base_r: 5.0,
+        base_h: 6.0,
+        pin_r: 3.0,
+        pin_h: 17.0,
+    },
+    shafts: QUARTZ_SHAFTS,
+    cutouts: &[],
+};
+
+/// A 56mm square quartz movement with a pendulum drive below its shaft, measured from the
+/// corner of its case
+const QUARTZ_PENDULUM: Movement = Movement {
+    name: "quartz_pendulum",
+    shaft: [28.0, 20.0],
+    posts: &[[-8.0, -8.0], [64.0, -8.0], [-8.0, 64.0], [64.0, 64.0]],
+    post: Post {
+        base_r: 5.0,
+        base_h: 6.0,
+        pin_r: 3.0,
+        pin_h: 17.0,
+    },
+    shafts: QUARTZ_SHAFTS,
+    cutouts: &[([28.0, 62.0], 5.0)],
+};
+
+#[derive(Clone, Copy, Debug)]
+struct Clock {
+    name: &'static str,
+    outer_d: f64,
+    movement: Movement,
+    /// Where the hand shaft sits in the movement's frame. The face is centered on it.
+    shaft: [f64; 2],
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::new("clock", 180.0, SKELETON)
+    }
+}
+
+impl Clock {
+    fn new(name: &'static str, outer_d: f64, movement: Movement) -> Self {
+        Self {
+            name,
+            outer_d,
+            movement,
+            shaft: movement.shaft,
+        }
+    }
+
+    fn outer_r(&self) -> f64 {
+        self.outer_d * 0.5
+    }
+
+    /// How much the reference proportions are scaled by
+    fn scale(&self) -> f64 {
+        self.outer_r() / REFERENCE_R
+    }
+
+    /// A point in the movement's frame, relative to the face center
+    fn locate(&self, [x, y]: [f64; 2]) -> [f64; 3] {
+        [x - self.shaft[0], y - self.shaft[1], 0.0]
+    }
+
+    fn posts(&self) -> impl Iterator<Item = [f64; 3]> + '_ {
+        self.movement.posts.iter().map(|p| self.locate(*p))
+    }
+
+    /// The face opening, pulled in when needed so the ring still reaches every post
+    fn inner_r(&self) -> f64 {
+        let base_r = self.movement.post.base_r;
+        self.posts()
+            .map(|[x, y, _]| x.hypot(y) - base_r - RIM_T)
+            .fold(self.outer_r() * INNER_RATIO, f64::min)
+    }
+
+    fn mount_radii(&self) -> [f64; 2] {
+        MOUNT_RATIOS.map(|ratio| self.outer_r() * ratio)
+    }
+
+    fn center_rim_r(&self) -> f64 {
+        self.outer_r() * CENTER_RIM_RATIO
+    }
+
+    /// How a part of the face splits into wedges that fit the printer's bed, with dovetails
+    /// across the ring spanning `z`
+    fn split(&self, z: [f64; 2]) -> Split {
+        // the outer rim's lip sticks out past the face
+        let count = split::wedges_to_fit(self.outer_r() + 1.0, printer().bed);
+        let [inner, outer] = self.mount_radii();
+
+        split::wedges(count)
+            .joint(Joint::Dovetail {
+                width: 6.0,
+                depth: 4.0,
+            })
+            .at(&[
+                (self.inner_r() + RIM_T + inner) * 0.5,
+                (self.center_rim_r() + outer) * 0.5,
+            ])
+            .z(z)
+    }
+
+    /// Checks the movement's posts land on the face ring
+    fn validate(&self) {
+        let name = self.name;
+        let movement = self.movement.name;
+        let base_r = self.movement.post.base_r;
+
+        for [x, y, _] in self.posts() {
+            let distance = x.hypot(y);
+            assert!(
+                distance + base_r <= self.outer_r() - RIM_T,
+                "{name}: a {movement} post at {distance:.1}mm from the shaft is off a {:.0}mm face",
+                self.outer_d
+            );
+            assert!(
+                distance - base_r >= self.inner_r(),
+                "{name}: a {movement} post at {distance:.1}mm from the shaft misses the face ring"
+            );
+        }
+
+        assert!(
+            self.inner_r() > 0.0,
+            "{name}: a {movement} movement leaves no opening in the face"
+        );
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Numerals {
+    Roman,
+    Arabic,
+}
+
+impl Numerals {
+    fn label(&self, hour: u32) -> String {
+        match self {
+            Self::Roman => [
+                "I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX", "X", "XI", "XII",
+            ][hour as usize - 1]
+                .to_string(),
+            Self::Arabic => hour.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Glyphs {
+    /// Traced artwork in `src/`, named after the lowercase numeral, e.g. `xii.svg`
+    Svg,
+    /// A font bundled in `fonts/`
+    Font(&'static str),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Orientation {
+    Upright,
+    /// Each numeral's top points away from the center
+    Radial,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Tick {
+    Dot {
+        r: f64,
+    },
+    /// A bar pointing at the center
+    Bar {
+        width: f64,
+        length: f64,
+    },
+}
+
+impl Tick {
+    fn shape(&self) -> Object<2> {
+        match *self {
+            Self::Dot { r } => circle(r).into(),
+            Self::Bar { width, length } => square([width, length]).center(true).into(),
+        }
+    }
+}
 
-fn dial_shape() -> Object<2> {
-    let tick_weight = 2.0;
-    let out = 71.5;
+#[derive(Clone, Copy, Debug)]
+enum HandStyle {
+    Spade,
+    Baton,
+    Breguet,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Hand {
+    Hour,
+    Minute,
+    Second,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Dial {
+    name: &'static str,
+    numerals: Numerals,
+    glyphs: Glyphs,
+    /// Only label 12, 3, 6 and 9, with hour ticks in between
+    quarters: bool,
+    orientation: Orientation,
+    /// The height of each numeral's bounding box
+    size: f64,
+    radius: f64,
+    hour_tick: Option<Tick>,
+    minute_tick: Option<Tick>,
+    tick_radius: f64,
+    hands: HandStyle,
+}
+
+impl Default for Dial {
+    fn default() -> Self {
+        Self {
+            name: "dial",
+            numerals: Numerals::Roman,
+            glyphs: Glyphs::Svg,
+            quarters: true,
+            orientation: Orientation::Radial,
+            size: 13.5,
+            radius: 71.5,
+            hour_tick: Some(Tick::Dot { r: 1.6 }),
+            minute_tick: None,
+            tick_radius: 71.5,
+            hands: HandStyle::Breguet,
+        }
+    }
+}
+
+impl Dial {
+    /// How far each hand reaches from the shaft, on the reference face
+    fn hand_length(&self, clock: &Clock, hand: Hand) -> f64 {
+        let minute = self.radius - self.size * 0.5 - HAND_MARGIN;
+        let length = match hand {
+            Hand::Minute => minute,
+            Hand::Hour => (minute * 0.75).min((clock.inner_r() - RIM_T) / clock.scale()),
+            Hand::Second => self.tick_radius,
+        };
+
+        length * clock.scale()
+    }
+
+    fn labelled(&self, hour: u32) -> bool {
+        !self.quarters || hour % 3 == 0
+    }
+
+    /// A numeral centered on its bounding box at the origin
+    fn glyph(&self, hour: u32) -> Object<2> {
+        let label = self.numerals.label(hour);
+
+        match self.glyphs {
+            Glyphs::Svg => {
+                let path = format!(
+                    "{}/src/{}.svg",
+                    env!("CARGO_MANIFEST_DIR"),
+                    label.to_lowercase()
+                );
+                assert!(
+                    std::path::Path::new(&path).exists(),
+                    "{}: there is no artwork for {label} at {path}, use a font instead",
+                    self.name
+                );
+
+                svg(path).trim().height(self.size).center().into_object()
+            }
+            Glyphs::Font(font) => {
+                let glyph = text(label)
+                    .font(font)
+                    .size(10.0)
+                    .halign("center")
+                    .valign("center");
+
+                // a font's size sets its ascender, so rescale until the glyph itself is `size` tall
+                let ys = glyph.contours().into_iter().flatten().map(|[_, y]| y);
+                let (min, max) = ys.fold((f64::MAX, f64::MIN), |(min, max), y| {
+                    (min.min(y), max.max(y))
+                });
+                let size = 10.0 * self.size / (max - min);
+
+                glyph.size(size).into_object()
+            }
+        }
+    }
+
+    /// The dial as seen from the front, with 12 at +y
+    fn shape(&self) -> Object<2> {
+        let at = |shape: Object<2>, degrees: f64, radius: f64| {
+            let (sin, cos) = degrees.to_radians().sin_cos();
+            shape >> translate([sin * radius, cos * radius, 0.0])
+        };
+
+        let mut shape = square(0.0).into_object();
+
+        for hour in 1..=12 {
+            let degrees = hour as f64 * 30.0;
+
+            if self.labelled(hour) {
+                let glyph = match self.orientation {
+                    Orientation::Upright => self.glyph(hour),
+                    Orientation::Radial => self.glyph(hour) >> rotate_z(-degrees),
+                };
+                shape += at(glyph, degrees, self.radius);
+            } else if let Some(tick) = self.hour_tick {
+                shape += at(
+                    tick.shape() >> rotate_z(-degrees),
+                    degrees,
+                    self.tick_radius,
+                );
+            }
+        }
+
+        if let Some(tick) = self.minute_tick {
+            for minute in (0..60).filter(|m| m % 5 != 0) {
+                let degrees = minute as f64 * 6.0;
+                shape += at(
+                    tick.shape() >> rotate_z(-degrees),
+                    degrees,
+                    self.tick_radius,
+                );
+            }
+        }
+
+        // the face prints front-down, so flip the dial to read from below with 12 opposite
+        // the movement cutout
+        shape >> mirror([0, 1, 0])
+    }
+}
+
+/// The hole a hand presses onto its shaft through
+fn hand_hole(clock: &Clock, hand: Hand) -> Object<2> {
+    let fit = printer().press_fit;
+    let shafts = clock.movement.shafts;
+
+    match hand {
+        Hand::Hour => circle((shafts.hour + fit) * 0.5).into(),
+        Hand::Minute => {
+            let [d, flats] = shafts.minute;
+            circle((d + fit) * 0.5) & square([flats + fit, d + fit]).center(true)
+        }
+        Hand::Second => {
+            let d = shafts.second.unwrap_or_else(|| {
+                panic!(
+                    "{}: a {} movement has no second hand",
+                    clock.name, clock.movement.name
+                )
+            });
+            circle((d + fit) * 0.5).into()
+        }
+    }
+}
 
-    let number = |name: &str, rot: i64| {
-        import::svg(format!("{}/src/{name}.svg", env!("CARGO_MANIFEST_DIR")))
-            >> translate([-250 / 2, -180, 0])
-            >> scale([0.11, 0.11, 1.0])
-            >> rotate([0, 180, rot])
+/// A hand pointing at 12 from its shaft at the origin
+fn hand_shape(style: HandStyle, length: f64, width: f64) -> Object<2> {
+    // every part runs from the pivot towards +y, which is `fwd` in rsolid
+    let shaft = |to: f64| square([width, to]).center(true) >> fwd(to * 0.5);
+
+    match style {
+        HandStyle::Baton => shaft(length),
+        HandStyle::Spade => {
+            let w = width * 1.8;
+            let spade = polygon([
+                [0.0, length],
+                [w, length * 0.8],
+                [0.0, length * 0.68],
+                [-w, length * 0.8],
+            ]);
+            shaft(length * 0.72) + spade
+        }
+        HandStyle::Breguet => {
+            let r = width * 2.0;
+            let center = length * 0.78;
+            let ring = (circle(r) - circle(r - width * 0.6)) >> fwd(center);
+            let tip = polygon([
+                [-width * 0.5, center + r - width * 0.3],
+                [width * 0.5, center + r - width * 0.3],
+                [0.0, length],
+            ]);
+            shaft(center - r + width * 0.3) + ring + tip
+        }
+    }
+}
+
+fn hand(clock: &Clock, dial: &Dial, hand: Hand) -> Object {
+    let length = dial.hand_length(clock, hand);
+    let hole = hand_hole(clock, hand);
+    let shafts = clock.movement.shafts;
+
+    let (shape, hub_d) = match hand {
+        Hand::Hour => (hand_shape(dial.hands, length, 3.0), shafts.hour),
+        Hand::Minute => (hand_shape(dial.hands, length, 2.2), shafts.minute[0]),
+        Hand::Second => {
+            // a thin needle balanced by a round tail
+            let d = shafts.second.unwrap_or_default();
+            let needle = square([0.8, length]).center(true) >> fwd(length * 0.5);
+            let tail = square([0.8, length * 0.2]).center(true) >> back(length * 0.1);
+            let weight = circle(2.0) >> back(length * 0.2);
+            (needle + tail + weight, d)
+        }
     };
 
-    let twelve = number("xii", 0) >> fwd(out) >> right(3);
-    let three = number("iii", 90) >> left(out);
-    let six = number("vi", 180) >> back(out + 2.8) >> left(2.4);
-    let nine = number("ix", -90) >> right(out);
+    let hub = circle(hub_d * 0.5 + HAND_HUB_WALL);
 
-    let mut shape = twelve + three + six + nine;
+    (shape + hub - hole) >> linear_extrude(HAND_T)
+}
 
-    for time in [1, 2, 4, 5, 7, 8, 10, 11] {
-        let degrees = (time * (360 / 12)) as f64;
-        let x = degrees.to_radians().sin() * out;
-        let y = degrees.to_radians().cos() * out;
-        let tick = circle(tick_weight * 0.8) >> translate([x, y, 0.0]);
-        shape += tick;
+fn hands(clock: &Clock, dial: &Dial) -> Vec<(Object, String)> {
+    let name = format!("{}_{}", clock.name, dial.name);
+
+    let mut out = vec![
+        (hand(clock, dial, Hand::Hour), format!("{name}_hand_hour")),
+        (
+            hand(clock, dial, Hand::Minute),
+            format!("{name}_hand_minute"),
+        ),
+    ];
+
+    if clock.movement.shafts.second.is_some() {
+        out.push((
+            hand(clock, dial, Hand::Second),
+            format!("{name}_hand_second"),
+        ));
     }
 
-    shape
+    out
 }
 
 fn screw(len: f64) -> Object {
@@ -42,29 +524,37 @@ fn screw(len: f64) -> Object {
         .into()
 }
 
-fn dial() -> Object {
-    let h = 2.0;
-    let ring = circle(OUTER_R);
-    let shape = ring - dial_shape() - face_cutout();
+fn dial(clock: &Clock, style: &Dial) -> Object {
+    let h = DIAL_Z[1] - DIAL_Z[0];
+    let ring = circle(clock.outer_r());
+    let scale_by = clock.scale();
+    let numerals = style.shape() >> scale([scale_by, scale_by, 1.0]);
+    let shape = ring - numerals - face_cutout(clock);
     let mut shape = shape >> linear_extrude(h) >> down(h);
 
     let len = 6.0;
-    face_mounts(|shift| {
+    face_mounts(clock, |shift| {
         let screw = screw(len) >> translate(shift) >> up(len);
         shape += screw;
     });
 
-    shape >> down(1.0)
+    shape >> up(DIAL_Z[1])
 }
 
-fn bottom_cutout(scale: f64) -> Object<2> {
-    circle(BOTTOM_CUTOUT_R * scale) >> back(52.82)
+fn movement_cutouts(clock: &Clock, scale: f64) -> Object<2> {
+    let mut shape = square(0.0).into_object();
+
+    for (center, r) in clock.movement.cutouts {
+        shape += circle(r * scale) >> translate(clock.locate(*center));
+    }
+
+    shape
 }
 
-fn face_mounts(mut f: impl FnMut([f64; 3])) {
+fn face_mounts(clock: &Clock, mut f: impl FnMut([f64; 3])) {
     let count = 8;
     let hole_deg = 360.0 / count as f64;
-    for out in [61.0, 84.0] {
+    for out in clock.mount_radii() {
         for i in 0..count {
             let degrees = i as f64 * hole_deg + hole_deg * 0.5;
             let x = degrees.to_radians().sin() * out;
@@ -74,37 +564,36 @@ fn face_mounts(mut f: impl FnMut([f64; 3])) {
     }
 }
 
-fn face_cutout() -> Object<2> {
-    let inner = circle(INNER_R);
-    let cutout = bottom_cutout(1.0);
+fn face_cutout(clock: &Clock) -> Object<2> {
+    let inner = circle(clock.inner_r());
+    let cutout = movement_cutouts(clock, 1.0);
 
     inner + cutout
 }
 
-fn face_shape() -> Object<2> {
-    let outer = circle(OUTER_R);
+fn face_shape(clock: &Clock) -> Object<2> {
+    let outer = circle(clock.outer_r());
 
-    let mut shape = outer - face_cutout();
+    let mut shape = outer - face_cutout(clock);
 
-    let hole_r = 2.0;
-    face_mounts(|shift| {
-        let hole = circle(hole_r) >> translate(shift);
+    face_mounts(clock, |shift| {
+        let hole = circle(MOUNT_HOLE_R) >> translate(shift);
         shape -= hole;
     });
 
     shape
 }
 
-fn face() -> Object {
-    face_shape() >> linear_extrude(1)
+fn face(clock: &Clock) -> Object {
+    face_shape(clock) >> linear_extrude(FACE_T)
 }
 
-fn outer_rim() -> Object {
-    let r = OUTER_R;
-    let h = 5.0;
-    let t = 2.0;
+fn outer_rim(clock: &Clock) -> Object {
+    let r = clock.outer_r();
+    let h = RIM_H;
+    let t = RIM_T;
     let lip_h = 1.0;
-    let outer = cylinder(h, 90).center(true);
+    let outer = cylinder(h, r).center(true);
     let lip = cone(lip_h, r, r + 1.0).center(true) >> up(h * 0.5 - lip_h * 0.5);
     let inner = cylinder(h + 2.0, r - t).center(true);
 
@@ -113,31 +602,31 @@ fn outer_rim() -> Object {
     rim >> up(h * 0.5)
 }
 
-fn inner_rim() -> Object {
-    let r = INNER_R;
-    let h = 5.0;
-    let t = 2.0;
-    let rim = circle(r + t) - face_cutout();
+fn inner_rim(clock: &Clock) -> Object {
+    let r = clock.inner_r();
+    let h = RIM_H;
+    let t = RIM_T;
+    let rim = circle(r + t) - face_cutout(clock);
     let rim = rim >> linear_extrude(h);
 
-    let bottom_rim = bottom_cutout(1.15) >> linear_extrude(h);
+    let bottom_rim = movement_cutouts(clock, 1.15) >> linear_extrude(h);
 
-    let mask = face_cutout() >> linear_extrude(100).center(true);
+    let mask = face_cutout(clock) >> linear_extrude(100).center(true);
 
     let lip_h = 1.0;
 
     let mut lip = cylinder(lip_h, r).center(true).into_object();
     lip -= cone(lip_h + 0.001, r, r - 1.0).center(true);
     lip = lip >> up(h - lip_h * 0.5);
-    lip -= bottom_cutout(1.0) >> linear_extrude(100).center(true);
+    lip -= movement_cutouts(clock, 1.0) >> linear_extrude(100).center(true);
 
     rim + bottom_rim - mask + lip
 }
 
-fn center_rim() -> Object {
-    let r = 73.5;
-    let t = 2.0;
-    let h = 5.0;
+fn center_rim(clock: &Clock) -> Object {
+    let r = clock.center_rim_r();
+    let t = RIM_T;
+    let h = RIM_H;
     let outer = circle(r);
     let inner = circle(r - t);
     let shape = outer - inner;
@@ -145,41 +634,123 @@ fn center_rim() -> Object {
     shape >> linear_extrude(h)
 }
 
-fn posts() -> Object {
+fn posts(clock: &Clock) -> Object {
+    let Post {
+        base_r,
+        base_h,
+        pin_r,
+        pin_h,
+    } = clock.movement.post;
+
     let post = {
-        let a = cylinder(13, 6);
+        let a = cylinder(base_h, base_r);
 
-        let h = 18.5;
-        let b = cylinder(h, 4);
-        let chamfer = mask::face::cylinder(4, mask::chamfer(1.0, 1.0)) >> up(h);
+        let b = cylinder(pin_h, pin_r);
+        let chamfer = mask::face::cylinder(pin_r, mask::chamfer(1.0, 1.0)) >> up(pin_h);
 
         a + b - chamfer
     };
 
-    let x = 60;
-    let y = 40.7;
-    let a = &post >> right(x) >> back(y);
-    let b = &post >> left(x) >> back(y);
-    let c = &post >> right(x) >> fwd(y);
-    let d = &post >> left(x) >> fwd(y);
+    let mut posts = empty().into_object();
+    for shift in clock.posts() {
+        posts += &post >> translate(shift);
+    }
 
-    a + b + c + d
+    posts
 }
 
 fn main() {
-    let face = face();
-    let dial = dial();
-    let main = face.clone() + dial.clone() + outer_rim() + inner_rim() + center_rim() + posts();
+    let clocks = [
+        Clock::default(),
+        Clock::new("clock_quartz", 150.0, QUARTZ),
+        Clock::new("clock_quartz_pendulum", 200.0, QUARTZ_PENDULUM),
+    ];
+
+    let dials = [
+        Dial::default(),
+        Dial {
+            name: "dial_arabic",
+            numerals: Numerals::Arabic,
+            glyphs: Glyphs::Font("DejaVu Sans:style=Bold"),
+            quarters: false,
+            orientation: Orientation::Upright,
+            size: 8.0,
+            radius: 69.0,
+            hour_tick: None,
+            minute_tick: Some(Tick::Bar {
+                width: 0.8,
+                length: 3.0,
+            }),
+            tick_radius: 78.0,
+            hands: HandStyle::Baton,
+        },
+        Dial {
+            name: "dial_roman",
+            glyphs: Glyphs::Font("DejaVu Sans"),
+            quarters: false,
+            size: 7.0,
+            radius: 70.0,
+            hour_tick: None,
+            minute_tick: Some(Tick::Dot { r: 0.6 }),
+            tick_radius: 78.0,
+            hands: HandStyle::Spade,
+            ..Default::default()
+        },
+    ];
 
     let settings = fragment_count(150).preview(25);
+    let targets = &["amf"];
 
-    let face = settings.apply(&face);
-    let dial = settings.apply(&dial);
-    let main = settings.apply(&main);
+    // each layer is split over its own height, so no joint bridges the gap between them
+    let export = |layers: &[(&Object, Split)], name: &str| {
+        let mut pieces: Vec<Object> = vec![];
+        for (part, split) in layers {
+            for (i, piece) in split.apply(part).into_iter().enumerate() {
+                match pieces.get_mut(i) {
+                    Some(joined) => *joined += piece,
+                    None => pieces.push(piece),
+                }
+            }
+        }
 
-    let targets = &["amf"];
+        for (i, piece) in pieces.iter().enumerate() {
+            let piece = settings.apply(piece);
+            let name = if pieces.len() == 1 {
+                name.to_string()
+            } else {
+                format!("{name}_{}", i + 1)
+            };
+            rsolid::export!(piece, &name, targets);
+        }
+    };
 
-    rsolid::export!(main, targets);
-    rsolid::export!(face, "face", targets);
-    rsolid::export!(dial, "dial", targets);
+    for clock in &clocks {
+        clock.validate();
+
+        let name = clock.name;
+        let face = face(clock);
+        let body =
+            face.clone() + outer_rim(clock) + inner_rim(clock) + center_rim(clock) + posts(clock);
+        let main_dial = dial(clock, &dials[0]);
+
+        let main = [
+            (&body, clock.split([0.0, FACE_T])),
+            (&main_dial, clock.split(DIAL_Z)),
+        ];
+        export(&main, name);
+        export(
+            &[(&face, clock.split([0.0, FACE_T]))],
+            &format!("{name}_face"),
+        );
+
+        for style in &dials {
+            let dial_name = format!("{name}_{}", style.name);
+            export(&[(&dial(clock, style), clock.split(DIAL_Z))], &dial_name);
+
+            for (hand, hand_name) in hands(clock, style) {
+                let hand = settings.apply(&hand);
+                rsolid::export!(hand, &hand_name, targets);
+            }
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use common::text;
 use rsolid::*;
 
 const HEIGHT: f64 = 25.75;
@@ -7,6 +8,11 @@ const CUTOUT_W: f64 = (WIDTH - INNER_W) * 0.5;
 const CUTOUT_H: f64 = 7.5;
 const THICKNESS: f64 = 0.4;
 const TAB_W: f64 = 1.25;
+/// The clear space between a glyph and the flap's edges and side cutouts
+const GLYPH_MARGIN: f64 = 1.5;
+const GLYPH_FONT: &str = "DejaVu Sans:style=Bold";
+/// How deep inlaid glyphs sink into the flap, from each face
+const INLAY_D: f64 = 0.12;
 
 fn shape() -> Object<2> {
     let mut base = square([WIDTH, HEIGHT]).center(true).into_object();
@@ -34,6 +40,130 @@ fn shape() -> Object<2> {
     base
 }
 
+/// The flap's face that glyphs may cover, from the pivot edge at -y up to the margin at the free
+/// edge, so the two halves of a glyph meet at the pivot
+fn face() -> Object<2> {
+    let w = INNER_W - GLYPH_MARGIN * 2.0;
+    let h = HEIGHT - GLYPH_MARGIN;
+    square([w, h]).center(true) >> back(GLYPH_MARGIN * 0.5)
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Charset {
+    Digits,
+    Minutes,
+    Letters,
+}
+
+impl Charset {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Digits => "digits",
+            Self::Minutes => "minutes",
+            Self::Letters => "letters",
+        }
+    }
+
+    fn glyphs(&self) -> Vec<String> {
+        match self {
+            Self::Digits => (0..10).map(|n| n.to_string()).collect(),
+            Self::Minutes => (0..60).map(|n| format!("{n:02}")).collect(),
+            Self::Letters => ('A'..='Z').map(String::from).collect(),
+        }
+    }
+}
+
+/// The full set of flaps for a character set, with each glyph split across two flaps
+struct Flaps {
+    charset: Charset,
+    font: &'static str,
+    glyphs: Vec<String>,
+    /// The font size and middle line shared by every glyph in the set
+    metrics: (f64, f64),
+}
+
+impl Flaps {
+    fn new(charset: Charset, font: &'static str) -> Self {
+        let glyphs = charset.glyphs();
+        let metrics = Self::metrics(&glyphs, font);
+
+        Self {
+            charset,
+            font,
+            glyphs,
+            metrics,
+        }
+    }
+
+    /// The font size that fits every glyph in the set, and the height of the shared middle line
+    /// above the baseline
+    fn metrics(glyphs: &[String], font: &str) -> (f64, f64) {
+        let mut widest: f64 = 0.0;
+        let [mut bottom, mut top] = [f64::MAX, f64::MIN];
+
+        for glyph in glyphs {
+            let contours = text(glyph).font(font).size(1.0).contours();
+            let [mut min_x, mut max_x] = [f64::MAX, f64::MIN];
+
+            for [x, y] in contours.iter().flatten() {
+                min_x = min_x.min(*x);
+                max_x = max_x.max(*x);
+                bottom = bottom.min(*y);
+                top = top.max(*y);
+            }
+
+            widest = widest.max(max_x - min_x);
+        }
+
+        // each flap shows half a glyph from the pivot edge, so it can be twice the face's height
+        let w = INNER_W - GLYPH_MARGIN * 2.0;
+        let h = (HEIGHT - GLYPH_MARGIN) * 2.0;
+        let size = (w / widest).min(h / (top - bottom));
+
+        (size, (top + bottom) * 0.5 * size)
+    }
+
+    /// A glyph centered on its middle line at the origin
+    fn glyph(&self, glyph: &str) -> Object<2> {
+        let (size, middle) = self.metrics;
+        text(glyph)
+            .font(self.font)
+            .size(size)
+            .halign("center")
+            .into_object()
+            >> fwd(middle)
+    }
+
+    /// The glyph halves on flap `idx`: the top of its glyph on the front and the bottom of the
+    /// previous one on the back. Standing above the pivot the front reads upright, and once the
+    /// flap falls below it the back is turned top to bottom.
+    fn halves(&self, idx: usize) -> [Object<2>; 2] {
+        let glyphs = &self.glyphs;
+        let previous = (idx + glyphs.len() - 1) % glyphs.len();
+        let edge = HEIGHT * 0.5;
+
+        let front = self.glyph(&glyphs[idx]) >> back(edge);
+        let back_half = self.glyph(&glyphs[previous]) >> mirror([0, 1, 0]) >> back(edge);
+
+        [front & face(), back_half & face()]
+    }
+
+    /// The flap body and its glyphs as separate bodies, for a filament each. The glyphs are sunk
+    /// flush into both faces, so the flap lies flat on the bed and doesn't rub its neighbors.
+    fn flap(&self, idx: usize) -> [Object; 2] {
+        let [front, back_half] = self.halves(idx);
+
+        let inlays = INLAY_D * 2.0;
+        assert!(
+            inlays < THICKNESS,
+            "{INLAY_D}mm inlays on both faces cut through a {THICKNESS}mm flap"
+        );
+        let glyphs = (front >> linear_extrude(INLAY_D) >> up(THICKNESS - INLAY_D))
+            + (back_half >> linear_extrude(INLAY_D));
+        [out() - glyphs.clone(), glyphs]
+    }
+}
+
 fn out() -> Object {
     shape() >> linear_extrude(THICKNESS)
 }
@@ -48,4 +178,25 @@ fn main() {
 
     let v = settings.apply(&out);
     rsolid::export!(v, targets);
+
+    // the flap outline, for cutting flaps from sheet instead of printing them
+    let profile = settings.apply(&shape());
+    rsolid::export!(profile, "flap_profile", &["dxf"]);
+
+    let sets = [
+        Flaps::new(Charset::Digits, GLYPH_FONT),
+        Flaps::new(Charset::Minutes, GLYPH_FONT),
+        Flaps::new(Charset::Letters, GLYPH_FONT),
+    ];
+
+    for flaps in &sets {
+        let set = flaps.charset.name();
+
+        for idx in 0..flaps.glyphs.len() {
+            let [flap, glyphs] = flaps.flap(idx).map(|body| settings.apply(&body));
+
+            rsolid::export!(flap, format!("flap_{set}_{idx:02}"), targets);
+            rsolid::export!(glyphs, format!("flap_{set}_{idx:02}_glyphs"), targets);
+        }
+    }
 }
@@ -1,65 +1,46 @@
+use common::assets;
 use rsolid::*;
 
-fn back_plate() -> Object {
-    import::stl("/home/cameron/Projects/3d-models/pendulum/Skeleton/Back_plate.stl")
-        >> fwd(41)
-        >> down(80)
+const CORNER_R: f64 = 7.5;
+const PLATE_H: f64 = 100.0;
+/// How far below `PLATE_H` the top corners sit
+const PLATE_TOP_SHIFT: f64 = 8.0;
+
+/// A skeleton plate, and where its mesh was placed when its masks and fill were measured
+struct Plate {
+    asset: &'static str,
+    name: &'static str,
+    /// How far the corners reach either side of the center
+    w: f64,
+    /// The `fwd` and `down` moves that put the mesh in the measured frame
+    place: [f64; 2],
+    fill: fn() -> Object,
 }
 
-fn middle_plate() -> Object {
-    import::stl("/home/cameron/Projects/3d-models/pendulum/Skeleton/Middle_plate.stl")
-        >> fwd(40.73)
-        >> down(80)
-}
+impl Plate {
+    /// The repaired mesh centered on its bounding box, with its fill added and corners rounded
+    fn model(&self) -> Object {
+        let mut mesh = assets::repaired(self.asset);
+        let shift = mesh.center();
 
-fn top_plate() -> Object {
-    import::stl("/home/cameron/Projects/3d-models/pendulum/Skeleton/Front_plate.stl")
-        >> fwd(72.3)
-        >> down(95.25)
-}
+        // undo the measured placement, then follow the mesh to its center
+        let [fwd_by, down_by] = self.place;
+        let unplace =
+            |part: Object| part >> back(fwd_by) >> up(down_by) >> translate(shift.map(|v| -v));
 
-fn mask() -> Object<2> {
-    mask::fillet(7.5)
+        mesh.into_object() + unplace((self.fill)()) - unplace(plate_masks(self.w, CORNER_R))
+    }
 }
 
-fn back_plate_masks() -> Object {
-    let w = 62.51;
-    let h = 100.0;
-    let top_shift = 8.0;
-
-    let top_mask = square([10, 10]).center(true) >> left(10.0);
-    let top_shape = mask() - top_mask;
-
-    let a = &top_shape >> rotate_y(180) >> left(w) >> fwd(h - top_shift);
-    let b = &top_shape >> right(w) >> fwd(h - top_shift);
-    let c = mask() >> rotate_z(180) >> left(w) >> back(h);
-    let d = mask() >> rotate_z(-90) >> right(w) >> back(h);
-
-    let shape = a + b + c + d;
-    shape >> linear_extrude(100).center(true)
+fn no_fill() -> Object {
+    empty().into_object()
 }
 
-fn middle_plate_masks() -> Object {
-    let w = 62.5;
-    let h = 100.0;
-    let top_shift = 8.0;
-
-    let top_mask = square([10, 10]).center(true) >> left(10.0);
-    let top_shape = mask() - top_mask;
-
-    let a = &top_shape >> rotate_y(180) >> left(w) >> fwd(h - top_shift);
-    let b = &top_shape >> right(w) >> fwd(h - top_shift);
-    let c = mask() >> rotate_z(180) >> left(w) >> back(h);
-    let d = mask() >> rotate_z(-90) >> right(w) >> back(h);
-
-    let shape = a + b + c + d;
-    shape >> linear_extrude(100).center(true)
-}
-
-fn top_plate_masks() -> Object {
-    let w = 62.51;
-    let h = 100.0;
-    let top_shift = 8.0;
+/// Rounds the four corners of a plate that reaches `w` either side of its center
+fn plate_masks(w: f64, r: f64) -> Object {
+    let h = PLATE_H;
+    let top_shift = PLATE_TOP_SHIFT;
+    let mask = || mask::fillet(r);
 
     let top_mask = square([10, 10]).center(true) >> left(10.0);
     let top_shape = mask() - top_mask;
@@ -73,6 +54,7 @@ fn top_plate_masks() -> Object {
     shape >> linear_extrude(100).center(true)
 }
 
+/// Patches for the top plate
 fn top_plate_fill() -> Object {
     let a = square([100.0, 28.0]).center(true) >> back(82);
     let b = square([40.0, 20.0]).center(true) >> rotate_z(30.0) >> back(70) >> left(30.0);
@@ -81,7 +63,7 @@ fn top_plate_fill() -> Object {
 
     let d = square([48.0, 15.0]).center(true) >> rotate_z(-33) >> fwd(73) >> left(24);
 
-    let e = triangle::right(12.0, 12.0) >> rotate_z(-90) >> fwd(85) >> left(43) >> dbg();
+    let e = triangle::right(12.0, 12.0) >> rotate_z(-90) >> fwd(85) >> left(43);
 
     let mut shape = b + c + d + e;
     shape += &shape >> mirror([1, 0, 0]);
@@ -91,10 +73,34 @@ fn top_plate_fill() -> Object {
 }
 
 fn main() {
-    let h = back_plate() - back_plate_masks().dbg();
-    //let h = middle_plate() - middle_plate_masks().dbg();
-    //let h = top_plate() + top_plate_fill() - top_plate_masks();
-    let out = h >> fragment_count(200).preview(50);
-
-    rsolid::export!(out, &["amf"]);
+    let plates = [
+        Plate {
+            asset: "skeleton_back_plate",
+            name: "back_plate",
+            w: 62.51,
+            place: [41.0, 80.0],
+            fill: no_fill,
+        },
+        Plate {
+            asset: "skeleton_middle_plate",
+            name: "middle_plate",
+            w: 62.5,
+            place: [40.73, 80.0],
+            fill: no_fill,
+        },
+        Plate {
+            asset: "skeleton_front_plate",
+            name: "top_plate",
+            w: 62.51,
+            place: [72.3, 95.25],
+            fill: top_plate_fill,
+        },
+    ];
+
+    let settings = fragment_count(200).preview(50);
+
+    for plate in &plates {
+        let part = settings.apply(&plate.model());
+        rsolid::export!(part, plate.name, &["amf"]);
+    }
 }
@@ -0,0 +1,94 @@
+use crate::mesh::Mesh;
+use rsolid::*;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, fs, path::PathBuf, sync::OnceLock};
+
+pub const ASSETS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../assets");
+pub const MANIFEST: &str = "manifest.toml";
+
+/// An external file the models build on, listed in `assets/manifest.toml`
+#[derive(Debug, Deserialize)]
+pub struct Asset {
+    /// The file name inside `assets/`
+    pub file: String,
+    /// Where the file came from, so it can be fetched again
+    pub source: String,
+    pub license: String,
+    pub sha256: Option<String>,
+}
+
+fn manifest() -> &'static BTreeMap<String, Asset> {
+    static MANIFEST_ENTRIES: OnceLock<BTreeMap<String, Asset>> = OnceLock::new();
+
+    MANIFEST_ENTRIES.get_or_init(|| {
+        let path = format!("{ASSETS_DIR}/{MANIFEST}");
+        let data =
+            fs::read_to_string(&path).unwrap_or_else(|err| panic!("could not read {path}: {err}"));
+        toml::from_str(&data).unwrap_or_else(|err| panic!("{path} is not a valid manifest: {err}"))
+    })
+}
+
+/// Looks up an asset by its manifest name and checks the file is there and unchanged
+pub fn asset(name: &str) -> PathBuf {
+    let asset = manifest().get(name).unwrap_or_else(|| {
+        let available = manifest().keys().collect::<Vec<_>>();
+        panic!("asset {name:?} is not in {ASSETS_DIR}/{MANIFEST} (available: {available:?})")
+    });
+
+    let path = PathBuf::from(ASSETS_DIR).join(&asset.file);
+    let display = path.display();
+
+    let data = fs::read(&path).unwrap_or_else(|err| {
+        panic!(
+            "asset {name:?} is missing from {display} ({err}); fetch it from {} ({})",
+            asset.source, asset.license
+        )
+    });
+
+    let actual = Sha256::digest(&data)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+
+    let expected = asset.sha256.as_deref().unwrap_or_else(|| {
+        panic!("asset {name:?} isn't pinned; add sha256 = {actual:?} to {ASSETS_DIR}/{MANIFEST}")
+    });
+
+    assert!(
+        expected.eq_ignore_ascii_case(&actual),
+        "asset {name:?} at {display} doesn't match the manifest (expected sha256 {expected}, got {actual}); fetch it again from {}",
+        asset.source
+    );
+
+    path
+}
+
+/// Reads a mesh asset, repairs it and centers it on its bounding box, resting on z = 0
+pub fn mesh(name: &str) -> Mesh {
+    let mut mesh = repaired(name);
+    mesh.center();
+    mesh
+}
+
+/// Reads a mesh asset and repairs it, leaving it where it was modelled
+pub fn repaired(name: &str) -> Mesh {
+    let mut mesh = Mesh::read(name, &asset(name));
+
+    let report = mesh.repair();
+    if !report.is_clean() {
+        eprintln!("{name}: {report}");
+    }
+    assert!(
+        report.non_manifold == 0,
+        "asset {name:?} has {} edges shared by more than two triangles",
+        report.non_manifold
+    );
+
+    mesh
+}
+
+/// Imports a mesh asset by its manifest name, repaired and centered
+pub fn stl(name: &str) -> Object {
+    mesh(name).into_object()
+}
@@ -0,0 +1,119 @@
+//! Prints the dimensions of a mesh so models can be fit to it without guessing.
+//!
+//! ```text
+//! cargo run -p common --bin measure -- <asset name or STL path> [--z <height>] [--svg <file>] [--dxf <file>]
+//! ```
+
+use common::{
+    assets,
+    mesh::{self, Mesh},
+};
+use std::{fmt::Write as _, fs, path::Path};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let mut input = None;
+    let mut z = None;
+    let mut svg = None;
+    let mut dxf = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--z" => {
+                let value = args.next().expect("--z needs a height");
+                z = Some(
+                    value
+                        .parse::<f64>()
+                        .unwrap_or_else(|_| panic!("--z {value:?} is not a number")),
+                );
+            }
+            "--svg" => svg = Some(args.next().expect("--svg needs a file")),
+            "--dxf" => dxf = Some(args.next().expect("--dxf needs a file")),
+            _ if input.is_none() => input = Some(arg),
+            _ => panic!("unexpected argument {arg:?}"),
+        }
+    }
+
+    let input = input.expect(
+        "usage: measure <asset name or STL path> [--z <height>] [--svg <file>] [--dxf <file>]",
+    );
+
+    let path = if Path::new(&input).exists() {
+        input.clone().into()
+    } else {
+        assets::asset(&input)
+    };
+
+    let mut mesh = Mesh::read(&input, &path);
+    let report = mesh.repair();
+    if !report.is_clean() {
+        println!("repairs: {report}");
+    }
+
+    let [min, max] = mesh.bounds();
+    let size = mesh.size();
+    println!("bounds:   {} .. {}", point(&min), point(&max));
+    println!("size:     {}", point(&size));
+    println!(
+        "center:   {}",
+        point(&[0, 1, 2].map(|i| (min[i] + max[i]) * 0.5))
+    );
+    println!("centroid: {}", point(&mesh.centroid()));
+
+    for (idx, (axis, spread)) in mesh.principal_axes().into_iter().enumerate() {
+        println!("axis {}:   {} (spread {spread:.3})", idx + 1, point(&axis));
+    }
+
+    // default to the middle of the part, which cuts through most holes
+    let z = z.unwrap_or((min[2] + max[2]) * 0.5);
+    let section = mesh.section(z);
+
+    println!("section at z = {z:.3}: {} outlines", section.len());
+
+    for (center, d) in mesh::holes(&section) {
+        println!(
+            "hole:     d = {d:.3} at [{:.3}, {:.3}]",
+            center[0], center[1]
+        );
+    }
+
+    if let Some(file) = svg {
+        fs::write(&file, section_svg(&mesh, &section))
+            .unwrap_or_else(|err| panic!("could not write {file}: {err}"));
+        println!("wrote the section to {file}");
+    }
+
+    if let Some(file) = dxf {
+        common::dxf::write(&file, &section);
+        println!("wrote the section to {file}");
+    }
+}
+
+fn point(p: &[f64; 3]) -> String {
+    format!("[{:.3}, {:.3}, {:.3}]", p[0], p[1], p[2])
+}
+
+/// Draws the section in millimeters, in the mesh's own coordinates
+fn section_svg(mesh: &Mesh, section: &[Vec<[f64; 2]>]) -> String {
+    let [min, max] = mesh.bounds();
+    let [w, h] = [max[0] - min[0], max[1] - min[1]];
+
+    // svg's y axis points down, so flip it to keep the drawing the right way up
+    let mut d = String::new();
+    for outline in section {
+        for (idx, [x, y]) in outline.iter().enumerate() {
+            let command = if idx == 0 { 'M' } else { 'L' };
+            write!(d, "{command}{x:.4},{:.4} ", -y).unwrap();
+        }
+        d.push_str("Z ");
+    }
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w:.4}mm\" height=\"{h:.4}mm\" \
+         viewBox=\"{:.4} {:.4} {w:.4} {h:.4}\">\n  \
+         <path d=\"{}\" fill=\"black\" fill-rule=\"evenodd\"/>\n</svg>\n",
+        min[0],
+        -max[1],
+        d.trim_end()
+    )
+}
@@ -0,0 +1,477 @@
+use crate::font::fill;
+use rsolid::*;
+use std::{
+    f64::consts::TAU,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Segments in a full circle; arcs get their share of these
+const CIRCLE_STEPS: usize = 64;
+/// Ends closer than this are joined when chaining lines and arcs into outlines
+const JOIN: f64 = 1e-4;
+
+pub struct Dxf {
+    path: PathBuf,
+    contours: Vec<Vec<[f64; 2]>>,
+}
+
+/// Reads the closed outlines of a DXF drawing in millimeters, from its LINE, ARC, CIRCLE,
+/// LWPOLYLINE and POLYLINE entities. Loose lines and arcs are chained end to end.
+pub fn dxf(path: impl Into<PathBuf>) -> Dxf {
+    let path = path.into();
+    let display = path.display();
+
+    let data =
+        fs::read_to_string(&path).unwrap_or_else(|err| panic!("could not read {display}: {err}"));
+
+    let pairs = pairs(&data).unwrap_or_else(|line| panic!("{display}: line {line} is malformed"));
+    let scale = units(&pairs).unwrap_or_else(|units| {
+        panic!("{display} uses $INSUNITS {units}, which isn't a length we know")
+    });
+
+    let mut contours = vec![];
+    let mut pieces = vec![];
+
+    let entities = entities(&pairs);
+    let mut iter = entities.iter();
+
+    while let Some((kind, group)) = iter.next() {
+        let value = |code: i32| number(&path, kind, group, code);
+
+        match *kind {
+            "LINE" => pieces.push(vec![[value(10), value(20)], [value(11), value(21)]]),
+            "ARC" => {
+                let start = value(50).to_radians();
+                let mut sweep = value(51).to_radians() - start;
+                if sweep <= 0.0 {
+                    sweep += TAU;
+                }
+                pieces.push(arc([value(10), value(20)], value(40), start, sweep));
+            }
+            "CIRCLE" => {
+                let mut circle = arc([value(10), value(20)], value(40), 0.0, TAU);
+                circle.pop();
+                contours.push(circle);
+            }
+            "LWPOLYLINE" => {
+                let closed = flags(group) & 1 != 0;
+                let vertices = lw_vertices(group)
+                    .unwrap_or_else(|| panic!("{display} has a malformed LWPOLYLINE"));
+                polyline(&vertices, closed, &mut contours, &mut pieces);
+            }
+            "POLYLINE" => {
+                let closed = flags(group) & 1 != 0;
+                let mut vertices = vec![];
+                for (kind, group) in iter.by_ref() {
+                    match *kind {
+                        "VERTEX" => {
+                            let value = |code: i32| number(&path, kind, group, code);
+                            let bulge = find(group, 42).map_or(0.0, |_| value(42));
+                            vertices.push(([value(10), value(20)], bulge));
+                        }
+                        "SEQEND" => break,
+                        other => panic!("{display} has a {other} inside a POLYLINE"),
+                    }
+                }
+                polyline(&vertices, closed, &mut contours, &mut pieces);
+            }
+            "SPLINE" | "ELLIPSE" | "INSERT" => {
+                panic!("{display} uses {kind} entities; explode or convert them to polylines")
+            }
+            // annotations, hatching and the like don't describe the outline
+            _ => {}
+        }
+    }
+
+    contours.extend(chain(pieces).unwrap_or_else(|[x, y]| {
+        panic!("{display} has an outline that isn't closed at [{x:.3}, {y:.3}]")
+    }));
+
+    for contour in &mut contours {
+        for point in contour.iter_mut() {
+            *point = point.map(|v| v * scale);
+        }
+    }
+
+    assert!(!contours.is_empty(), "{display} doesn't draw any outlines");
+
+    Dxf { path, contours }
+}
+
+impl Dxf {
+    pub fn contours(&self) -> &[Vec<[f64; 2]>] {
+        &self.contours
+    }
+
+    /// The drawing's bounds in millimeters, as `[min, max]`
+    pub fn bounds(&self) -> [[f64; 2]; 2] {
+        bounds(&self.contours)
+    }
+
+    /// Moves the drawing so its bounds are centered on the origin
+    pub fn center(mut self) -> Self {
+        let [min, max] = self.bounds();
+        let mid = [0, 1].map(|i| (min[i] + max[i]) * 0.5);
+
+        for contour in &mut self.contours {
+            for point in contour.iter_mut() {
+                *point = [point[0] - mid[0], point[1] - mid[1]];
+            }
+        }
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn into_object(self) -> Object<2> {
+        fill(&self.contours)
+    }
+}
+
+impl From<Dxf> for Object<2> {
+    fn from(dxf: Dxf) -> Self {
+        dxf.into_object()
+    }
+}
+
+/// Writes closed outlines in millimeters as R12 polylines, which every CAD tool and laser cutter
+/// reads. Shapes built from primitives are written with the `dxf` export target instead.
+pub fn write(path: impl AsRef<Path>, contours: &[Vec<[f64; 2]>]) {
+    let path = path.as_ref();
+    let mut out = String::new();
+
+    let mut pair = |code: i32, value: &dyn std::fmt::Display| {
+        writeln!(out, "{code}\n{value}").unwrap();
+    };
+
+    pair(0, &"SECTION");
+    pair(2, &"HEADER");
+    pair(9, &"$INSUNITS");
+    pair(70, &4);
+    pair(0, &"ENDSEC");
+    pair(0, &"SECTION");
+    pair(2, &"ENTITIES");
+
+    for contour in contours {
+        pair(0, &"POLYLINE");
+        pair(8, &0);
+        pair(66, &1);
+        pair(10, &0.0);
+        pair(20, &0.0);
+        pair(30, &0.0);
+        pair(70, &1);
+        for [x, y] in contour {
+            pair(0, &"VERTEX");
+            pair(8, &0);
+            pair(10, x);
+            pair(20, y);
+            pair(30, &0.0);
+        }
+        pair(0, &"SEQEND");
+        pair(8, &0);
+    }
+
+    pair(0, &"ENDSEC");
+    pair(0, &"EOF");
+
+    fs::write(path, out).unwrap_or_else(|err| panic!("could not write {}: {err}", path.display()));
+}
+
+/// The drawing's group codes and values, or the line that isn't one
+fn pairs(data: &str) -> Result<Vec<(i32, &str)>, usize> {
+    let lines = data.trim_end().lines().map(str::trim).collect::<Vec<_>>();
+
+    lines
+        .chunks(2)
+        .enumerate()
+        .map(|(idx, pair)| match pair {
+            [code, value] => code
+                .parse()
+                .map(|code| (code, *value))
+                .map_err(|_| idx * 2 + 1),
+            _ => Err(idx * 2 + 1),
+        })
+        .collect()
+}
+
+/// Millimeters per drawing unit from the header, or the `$INSUNITS` value we can't convert.
+/// Unitless drawings are taken to be in millimeters.
+fn units(pairs: &[(i32, &str)]) -> Result<f64, String> {
+    let Some(idx) = pairs.iter().position(|pair| *pair == (9, "$INSUNITS")) else {
+        return Ok(1.0);
+    };
+
+    match pairs.get(idx + 1) {
+        Some((70, "0" | "4")) | None => Ok(1.0),
+        Some((70, "1")) => Ok(25.4),
+        Some((70, "2")) => Ok(304.8),
+        Some((70, "5")) => Ok(10.0),
+        Some((70, "6")) => Ok(1000.0),
+        Some((_, units)) => Err(units.to_string()),
+    }
+}
+
+/// The entities in the ENTITIES section, as their type and group codes
+fn entities<'a>(pairs: &[(i32, &'a str)]) -> Vec<(&'a str, Vec<(i32, &'a str)>)> {
+    let start = pairs
+        .windows(2)
+        .position(|w| w == [(0, "SECTION"), (2, "ENTITIES")])
+        .map_or(pairs.len(), |idx| idx + 2);
+
+    let mut entities: Vec<(&str, Vec<(i32, &str)>)> = vec![];
+
+    for &(code, value) in &pairs[start..] {
+        if code == 0 {
+            if value == "ENDSEC" {
+                break;
+            }
+            entities.push((value, vec![]));
+        } else if let Some((_, group)) = entities.last_mut() {
+            group.push((code, value));
+        }
+    }
+
+    entities
+}
+
+fn find<'a>(group: &[(i32, &'a str)], code: i32) -> Option<&'a str> {
+    group
+        .iter()
+        .find(|(other, _)| *other == code)
+        .map(|(_, value)| *value)
+}
+
+fn number(path: &Path, kind: &str, group: &[(i32, &str)], code: i32) -> f64 {
+    let value = find(group, code)
+        .unwrap_or_else(|| panic!("{}: a {kind} is missing group code {code}", path.display()));
+
+    value.parse().unwrap_or_else(|_| {
+        panic!(
+            "{}: a {kind} has {value:?} for group code {code}",
+            path.display()
+        )
+    })
+}
+
+fn flags(group: &[(i32, &str)]) -> u32 {
+    find(group, 70).and_then(|v| v.parse().ok()).unwrap_or(0)
+}
+
+/// An LWPOLYLINE's vertices and the bulge of the segment after each one
+fn lw_vertices(group: &[(i32, &str)]) -> Option<Vec<([f64; 2], f64)>> {
+    let mut vertices: Vec<([f64; 2], f64)> = vec![];
+
+    for &(code, value) in group {
+        match code {
+            10 => vertices.push(([value.parse().ok()?, f64::NAN], 0.0)),
+            20 => vertices.last_mut()?.0[1] = value.parse().ok()?,
+            42 => vertices.last_mut()?.1 = value.parse().ok()?,
+            _ => {}
+        }
+    }
+
+    vertices
+        .iter()
+        .all(|(point, _)| !point[1].is_nan())
+        .then_some(vertices)
+}
+
+/// Points along an arc from `start` radians, sweeping counterclockwise by `sweep`
+fn arc(center: [f64; 2], r: f64, start: f64, sweep: f64) -> Vec<[f64; 2]> {
+    let steps = ((sweep.abs() / TAU * CIRCLE_STEPS as f64).ceil() as usize).max(1);
+
+    (0..=steps)
+        .map(|step| {
+            let (sin, cos) = (start + sweep * step as f64 / steps as f64).sin_cos();
+            [center[0] + r * cos, center[1] + r * sin]
+        })
+        .collect()
+}
+
+/// Expands a polyline's bulges into arcs and files it as a contour or a piece to chain
+fn polyline(
+    vertices: &[([f64; 2], f64)],
+    closed: bool,
+    contours: &mut Vec<Vec<[f64; 2]>>,
+    pieces: &mut Vec<Vec<[f64; 2]>>,
+) {
+    let Some((first, _)) = vertices.first() else {
+        return;
+    };
+
+    let segments = if closed {
+        vertices.len()
+    } else {
+        vertices.len() - 1
+    };
+
+    let mut points = vec![*first];
+    for idx in 0..segments {
+        let (a, bulge) = vertices[idx];
+        let (b, _) = vertices[(idx + 1) % vertices.len()];
+
+        if bulge == 0.0 {
+            points.push(b);
+            continue;
+        }
+
+        // the bulge is the tangent of a quarter of the arc's sweep, negative for clockwise
+        let sweep = bulge.atan() * 4.0;
+        let chord = (b[0] - a[0]).hypot(b[1] - a[1]);
+        let r = chord * 0.5 / (sweep * 0.5).sin().abs();
+        let mid = [(a[0] + b[0]) * 0.5, (a[1] + b[1]) * 0.5];
+        // signed distance from the chord to the center, to its left
+        let offset = chord * 0.5 / (sweep * 0.5).tan();
+        let normal = [-(b[1] - a[1]) / chord, (b[0] - a[0]) / chord];
+        let center = [mid[0] + normal[0] * offset, mid[1] + normal[1] * offset];
+        let start = (a[1] - center[1]).atan2(a[0] - center[0]);
+
+        points.extend(arc(center, r, start, sweep).into_iter().skip(1));
+    }
+
+    if closed {
+        points.pop();
+        if points.len() > 2 {
+            contours.push(points);
+        }
+    } else {
+        pieces.push(points);
+    }
+}
+
+/// Joins open pieces end to end into closed contours, or returns where one is left open
+fn chain(mut pieces: Vec<Vec<[f64; 2]>>) -> Result<Vec<Vec<[f64; 2]>>, [f64; 2]> {
+    let near = |a: [f64; 2], b: [f64; 2]| (a[0] - b[0]).hypot(a[1] - b[1]) <= JOIN;
+    let mut contours = vec![];
+
+    while let Some(mut contour) = pieces.pop() {
+        loop {
+            let first = contour[0];
+            let last = *contour.last().unwrap();
+
+            if contour.len() > 2 && near(first, last) {
+                contour.pop();
+                contours.push(contour);
+                break;
+            }
+
+            let next = pieces
+                .iter()
+                .position(|piece| near(piece[0], last) || near(*piece.last().unwrap(), last));
+            let Some(next) = next else {
+                return Err(last);
+            };
+
+            let mut piece = pieces.swap_remove(next);
+            if !near(piece[0], last) {
+                piece.reverse();
+            }
+            contour.extend(piece.into_iter().skip(1));
+        }
+    }
+
+    Ok(contours)
+}
+
+fn bounds(contours: &[Vec<[f64; 2]>]) -> [[f64; 2]; 2] {
+    contours
+        .iter()
+        .flatten()
+        .fold([[f64::MAX; 2], [f64::MIN; 2]], |[min, max], [x, y]| {
+            [
+                [min[0].min(*x), min[1].min(*y)],
+                [max[0].max(*x), max[1].max(*y)],
+            ]
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(actual: [[f64; 2]; 2], expected: [[f64; 2]; 2]) {
+        let near = (0..2).all(|i| (0..2).all(|j| (actual[i][j] - expected[i][j]).abs() < 1e-6));
+        assert!(near, "expected {expected:?}, got {actual:?}");
+    }
+
+    #[test]
+    fn pairs_are_codes_then_values() {
+        assert_eq!(
+            pairs("  0\nSECTION\n  2\nHEADER\n"),
+            Ok(vec![(0, "SECTION"), (2, "HEADER")])
+        );
+        assert_eq!(pairs("0\nSECTION\nx\nHEADER\n"), Err(3));
+        assert_eq!(pairs("0\nSECTION\n2\n"), Err(3));
+    }
+
+    #[test]
+    fn units_scale_to_millimeters() {
+        let header = |units: &'static str| vec![(9, "$INSUNITS"), (70, units)];
+
+        assert_eq!(units(&header("1")), Ok(25.4));
+        assert_eq!(units(&header("4")), Ok(1.0));
+        assert_eq!(units(&header("0")), Ok(1.0));
+        assert_eq!(units(&[]), Ok(1.0));
+        assert_eq!(units(&header("3")), Err("3".to_string()));
+    }
+
+    #[test]
+    fn bulges_round_off_the_nozzle_stadium() {
+        let nozzle = dxf(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../can-attachment/src/nozzle.dxf"
+        ));
+        let [contour] = nozzle.contours() else {
+            panic!("the nozzle is one outline");
+        };
+
+        // a 20 x 14 rectangle with a half circle bulging out of each end
+        assert_near(nozzle.bounds(), [[-17.0, -7.0], [17.0, 7.0]]);
+        for &[x, y] in contour.iter().filter(|[x, _]| x.abs() > 10.0) {
+            let r = (x.abs() - 10.0).hypot(y);
+            assert!(
+                (r - 7.0).abs() < 1e-6,
+                "[{x}, {y}] is {r} from its end's center"
+            );
+        }
+    }
+
+    #[test]
+    fn chain_closes_loose_lines_and_arcs() {
+        // the line runs backwards, so it's turned round to meet the arc
+        let line = vec![[10.0, 0.0], [0.0, 0.0]];
+        let arc = arc([5.0, 0.0], 5.0, 0.0, TAU * 0.5);
+
+        let contours = chain(vec![line, arc]).unwrap();
+        let [contour] = &contours[..] else {
+            panic!("a line and an arc make one outline");
+        };
+        assert_near(bounds(&contours), [[0.0, 0.0], [10.0, 5.0]]);
+        assert_eq!(contour.len(), CIRCLE_STEPS / 2 + 1);
+    }
+
+    #[test]
+    fn chain_reports_where_an_outline_is_open() {
+        let pieces = vec![
+            vec![[0.0, 0.0], [10.0, 0.0]],
+            vec![[10.0, 0.0], [10.0, 10.0]],
+        ];
+        assert_eq!(chain(pieces), Err([10.0, 10.0]));
+    }
+
+    #[test]
+    fn written_outlines_read_back() {
+        let contours = vec![
+            vec![[0.0, 0.0], [10.5, 0.0], [10.5, 5.25], [0.0, 5.25]],
+            vec![[2.0, 1.0], [4.0, 1.0], [3.0, 0.1]],
+        ];
+        let path = std::env::temp_dir().join("common-dxf-round-trip.dxf");
+
+        write(&path, &contours);
+        assert_eq!(dxf(&path).contours(), &contours[..]);
+    }
+}
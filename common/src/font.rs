@@ -0,0 +1,319 @@
+use rsolid::*;
+use std::{fs, path::PathBuf, sync::OnceLock};
+use ttf_parser::{name_id, Face, OutlineBuilder};
+
+pub const FONTS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../fonts");
+pub const DEFAULT_FONT: &str = "DejaVu Sans";
+
+const CURVE_STEPS: usize = 8;
+
+pub struct Font {
+    pub family: String,
+    pub style: String,
+    pub path: PathBuf,
+    data: Vec<u8>,
+}
+
+impl Font {
+    fn face(&self) -> Face<'_> {
+        Face::parse(&self.data, 0).unwrap()
+    }
+
+    pub fn name(&self) -> String {
+        format!("{}:style={}", self.family, self.style)
+    }
+}
+
+fn fonts() -> &'static [Font] {
+    static FONTS: OnceLock<Vec<Font>> = OnceLock::new();
+
+    FONTS.get_or_init(|| {
+        let mut paths = fs::read_dir(FONTS_DIR)
+            .unwrap_or_else(|err| panic!("could not read {FONTS_DIR}: {err}"))
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| {
+                path.extension().is_some_and(|ext| {
+                    ext.eq_ignore_ascii_case("ttf") || ext.eq_ignore_ascii_case("otf")
+                })
+            })
+            .collect::<Vec<_>>();
+
+        // keep the lookup independent of directory iteration order
+        paths.sort();
+
+        paths
+            .into_iter()
+            .map(|path| {
+                let data = fs::read(&path)
+                    .unwrap_or_else(|err| panic!("could not read {}: {err}", path.display()));
+                let face = Face::parse(&data, 0)
+                    .unwrap_or_else(|err| panic!("{} is not a valid font: {err}", path.display()));
+
+                let name = |ids: [u16; 2]| {
+                    ids.into_iter().find_map(|id| {
+                        face.names()
+                            .into_iter()
+                            .find(|name| name.name_id == id && name.is_unicode())
+                            .and_then(|name| name.to_string())
+                    })
+                };
+
+                let family = name([name_id::TYPOGRAPHIC_FAMILY, name_id::FAMILY])
+                    .unwrap_or_else(|| panic!("{} has no family name", path.display()));
+                let style = name([name_id::TYPOGRAPHIC_SUBFAMILY, name_id::SUBFAMILY])
+                    .unwrap_or_else(|| "Regular".to_string());
+
+                Font {
+                    family,
+                    style,
+                    path,
+                    data,
+                }
+            })
+            .collect()
+    })
+}
+
+/// Looks up a font bundled in `fonts/` by its OpenSCAD-style name, e.g. `"DejaVu Sans:style=Bold"`.
+///
+/// System fonts are never consulted so exports are the same on every machine.
+pub fn font(name: &str) -> &'static Font {
+    let (family, style) = name.split_once(":style=").unwrap_or((name, ""));
+
+    let matches = |font: &&Font| {
+        font.family.eq_ignore_ascii_case(family.trim())
+            && if style.is_empty() {
+                ["Regular", "Book", "Normal"]
+                    .iter()
+                    .any(|s| font.style.eq_ignore_ascii_case(s))
+            } else {
+                font.style.eq_ignore_ascii_case(style.trim())
+            }
+    };
+
+    fonts().iter().find(matches).unwrap_or_else(|| {
+        let available = fonts().iter().map(Font::name).collect::<Vec<_>>();
+        panic!("font {name:?} is not in {FONTS_DIR} (available: {available:?})")
+    })
+}
+
+pub struct Text {
+    content: String,
+    font: String,
+    size: f64,
+    halign: String,
+    valign: String,
+}
+
+pub fn text(content: impl Into<String>) -> Text {
+    Text {
+        content: content.into(),
+        font: DEFAULT_FONT.to_string(),
+        size: 10.0,
+        halign: "left".to_string(),
+        valign: "baseline".to_string(),
+    }
+}
+
+impl Text {
+    pub fn font(mut self, font: impl Into<String>) -> Self {
+        self.font = font.into();
+        self
+    }
+
+    /// The height of the font's ascender, matching OpenSCAD's `size`
+    pub fn size(mut self, size: f64) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn halign(mut self, halign: impl Into<String>) -> Self {
+        self.halign = halign.into();
+        self
+    }
+
+    pub fn valign(mut self, valign: impl Into<String>) -> Self {
+        self.valign = valign.into();
+        self
+    }
+
+    pub fn contours(&self) -> Vec<Vec<[f64; 2]>> {
+        let font = font(&self.font);
+        let face = font.face();
+
+        let mut outline = Outline {
+            scale: self.size / face.ascender() as f64,
+            ..Default::default()
+        };
+
+        for c in self.content.chars() {
+            let glyph = face
+                .glyph_index(c)
+                .unwrap_or_else(|| panic!("{} has no glyph for {c:?}", font.name()));
+
+            face.outline_glyph(glyph, &mut outline);
+            outline.close();
+
+            outline.offset += face.glyph_hor_advance(glyph).unwrap_or(0) as f64 * outline.scale;
+        }
+
+        let mut contours = outline.contours;
+
+        let (min, max) = bounds(&contours);
+
+        let x = match self.halign.as_str() {
+            "left" => 0.0,
+            "center" => -(min[0] + max[0]) * 0.5,
+            "right" => -outline.offset,
+            other => panic!("unknown halign {other:?}"),
+        };
+
+        let y = match self.valign.as_str() {
+            "baseline" => 0.0,
+            "top" => -max[1],
+            "center" => -(min[1] + max[1]) * 0.5,
+            "bottom" => -min[1],
+            other => panic!("unknown valign {other:?}"),
+        };
+
+        for point in contours.iter_mut().flatten() {
+            point[0] += x;
+            point[1] += y;
+        }
+
+        contours
+    }
+
+    pub fn into_object(self) -> Object<2> {
+        fill(&self.contours())
+    }
+}
+
+impl From<Text> for Object<2> {
+    fn from(text: Text) -> Self {
+        text.into_object()
+    }
+}
+
+#[derive(Default)]
+struct Outline {
+    contours: Vec<Vec<[f64; 2]>>,
+    current: Vec<[f64; 2]>,
+    offset: f64,
+    scale: f64,
+}
+
+impl Outline {
+    fn point(&self, x: f32, y: f32) -> [f64; 2] {
+        [self.offset + x as f64 * self.scale, y as f64 * self.scale]
+    }
+
+    fn last(&self) -> [f64; 2] {
+        *self.current.last().unwrap()
+    }
+}
+
+impl OutlineBuilder for Outline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.close();
+        self.current.push(self.point(x, y));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.current.push(self.point(x, y));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let [p0, p1, p2] = [self.last(), self.point(x1, y1), self.point(x, y)];
+        for step in 1..=CURVE_STEPS {
+            let t = step as f64 / CURVE_STEPS as f64;
+            let u = 1.0 - t;
+            self.current
+                .push([0, 1].map(|i| u * u * p0[i] + 2.0 * u * t * p1[i] + t * t * p2[i]));
+        }
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let [p0, p1, p2, p3] = [
+            self.last(),
+            self.point(x1, y1),
+            self.point(x2, y2),
+            self.point(x, y),
+        ];
+        for step in 1..=CURVE_STEPS {
+            let t = step as f64 / CURVE_STEPS as f64;
+            let u = 1.0 - t;
+            self.current.push([0, 1].map(|i| {
+                u * u * u * p0[i]
+                    + 3.0 * u * u * t * p1[i]
+                    + 3.0 * u * t * t * p2[i]
+                    + t * t * t * p3[i]
+            }));
+        }
+    }
+
+    fn close(&mut self) {
+        let contour = std::mem::take(&mut self.current);
+        if contour.len() > 2 {
+            self.contours.push(contour);
+        }
+    }
+}
+
+fn bounds(contours: &[Vec<[f64; 2]>]) -> ([f64; 2], [f64; 2]) {
+    contours
+        .iter()
+        .flatten()
+        .fold(([f64::MAX; 2], [f64::MIN; 2]), |(min, max), [x, y]| {
+            (
+                [min[0].min(*x), min[1].min(*y)],
+                [max[0].max(*x), max[1].max(*y)],
+            )
+        })
+}
+
+/// Fills closed contours, treating those nested an odd number of times as holes
+pub(crate) fn fill(contours: &[Vec<[f64; 2]>]) -> Object<2> {
+    let mut nested = contours
+        .iter()
+        .enumerate()
+        .map(|(idx, contour)| {
+            let depth = contours
+                .iter()
+                .enumerate()
+                .filter(|(other, outer)| *other != idx && contains(outer, contour[0]))
+                .count();
+            (depth, contour)
+        })
+        .collect::<Vec<_>>();
+
+    nested.sort_by_key(|(depth, _)| *depth);
+
+    let mut out = square(0.0).into_object();
+
+    for (depth, contour) in nested {
+        if depth % 2 == 0 {
+            out += polygon(contour.clone());
+        } else {
+            out -= polygon(contour.clone());
+        }
+    }
+
+    out
+}
+
+pub(crate) fn contains(contour: &[[f64; 2]], [x, y]: [f64; 2]) -> bool {
+    let mut inside = false;
+    let mut prev = contour[contour.len() - 1];
+
+    for &point in contour {
+        if (point[1] > y) != (prev[1] > y)
+            && x < (prev[0] - point[0]) * (y - point[1]) / (prev[1] - point[1]) + point[0]
+        {
+            inside = !inside;
+        }
+        prev = point;
+    }
+
+    inside
+}
@@ -0,0 +1,267 @@
+use crate::font::{text, DEFAULT_FONT};
+use rsolid::*;
+
+pub const ENV: &str = "LABELS";
+
+#[macro_export]
+macro_rules! version {
+    () => {
+        concat!("v", env!("CARGO_PKG_VERSION"))
+    };
+}
+
+/// Labels are opt-in; set `LABELS=1` to deboss them into exported parts.
+pub fn enabled() -> bool {
+    std::env::var(ENV).is_ok_and(|v| !v.is_empty() && v != "0")
+}
+
+#[derive(Clone, Debug)]
+pub enum Region {
+    Rect { center: [f64; 2], size: [f64; 2] },
+    Circle { center: [f64; 2], r: f64 },
+    Polygon(Vec<[f64; 2]>),
+}
+
+impl Region {
+    pub fn rect(size: [f64; 2]) -> Self {
+        Self::Rect {
+            center: [0.0; 2],
+            size,
+        }
+    }
+
+    pub fn circle(r: f64) -> Self {
+        Self::Circle {
+            center: [0.0; 2],
+            r,
+        }
+    }
+
+    fn contains(&self, [x, y]: [f64; 2], margin: f64) -> bool {
+        match self {
+            Self::Rect { center, size } => {
+                (x - center[0]).abs() <= size[0] * 0.5 - margin
+                    && (y - center[1]).abs() <= size[1] * 0.5 - margin
+            }
+            Self::Circle { center, r } => (x - center[0]).hypot(y - center[1]) <= r - margin,
+            Self::Polygon(points) => {
+                crate::font::contains(points, [x, y])
+                    && points
+                        .iter()
+                        .zip(points.iter().cycle().skip(1))
+                        .all(|(a, b)| segment_distance(*a, *b, [x, y]) >= margin)
+            }
+        }
+    }
+
+    fn overlaps(&self, [min, max]: [[f64; 2]; 2], margin: f64) -> bool {
+        let min = [min[0] - margin, min[1] - margin];
+        let max = [max[0] + margin, max[1] + margin];
+
+        match self {
+            Self::Rect { center, size } => (0..2)
+                .all(|i| center[i] - size[i] * 0.5 < max[i] && center[i] + size[i] * 0.5 > min[i]),
+            Self::Circle { center, r } => {
+                let nearest = [0, 1].map(|i| center[i].clamp(min[i], max[i]));
+                (nearest[0] - center[0]).hypot(nearest[1] - center[1]) < *r
+            }
+            Self::Polygon(points) => {
+                let rect = Self::Rect {
+                    center: [(min[0] + max[0]) * 0.5, (min[1] + max[1]) * 0.5],
+                    size: [max[0] - min[0], max[1] - min[1]],
+                };
+                points.iter().any(|p| rect.contains(*p, 0.0))
+                    || corners([min, max])
+                        .into_iter()
+                        .any(|c| crate::font::contains(points, c))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Side {
+    Top,
+    Underside,
+}
+
+pub struct Label {
+    content: String,
+    font: String,
+    size: f64,
+    depth: f64,
+    margin: f64,
+    center: [f64; 2],
+    rotation: f64,
+    face: Option<(Side, f64, Region)>,
+    keep_out: Vec<Region>,
+}
+
+pub fn label(content: impl Into<String>) -> Label {
+    Label {
+        content: content.into(),
+        font: DEFAULT_FONT.to_string(),
+        size: 3.0,
+        depth: 0.4,
+        margin: 0.8,
+        center: [0.0; 2],
+        rotation: 0.0,
+        face: None,
+        keep_out: vec![],
+    }
+}
+
+impl Label {
+    pub fn font(mut self, font: impl Into<String>) -> Self {
+        self.font = font.into();
+        self
+    }
+
+    pub fn size(mut self, size: f64) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn depth(mut self, depth: f64) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    pub fn margin(mut self, margin: f64) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    pub fn at(mut self, center: [f64; 2]) -> Self {
+        self.center = center;
+        self
+    }
+
+    pub fn rotate(mut self, degrees: f64) -> Self {
+        self.rotation = degrees;
+        self
+    }
+
+    /// Places the label on an upward facing face at height `z`, inside `outline`
+    pub fn top(mut self, z: f64, outline: Region) -> Self {
+        self.face = Some((Side::Top, z, outline));
+        self
+    }
+
+    /// Places the label on a downward facing face at height `z`, mirrored so it reads
+    /// correctly when the part is flipped over
+    pub fn underside(mut self, z: f64, outline: Region) -> Self {
+        self.face = Some((Side::Underside, z, outline));
+        self
+    }
+
+    /// Marks an area of the face the label must stay clear of, like a cavity or hole
+    pub fn keep_out(mut self, region: Region) -> Self {
+        self.keep_out.push(region);
+        self
+    }
+
+    fn text(&self) -> crate::font::Text {
+        text(self.content.clone())
+            .font(self.font.clone())
+            .size(self.size)
+            .halign("center")
+            .valign("center")
+    }
+
+    fn transform(&self, [x, y]: [f64; 2], mirrored: bool) -> [f64; 2] {
+        let x = if mirrored { -x } else { x };
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        [
+            self.center[0] + x * cos - y * sin,
+            self.center[1] + x * sin + y * cos,
+        ]
+    }
+
+    /// Checks the label fits its face and returns the volume to subtract from the part
+    pub fn cut(&self) -> Object {
+        let (side, z, outline) = self
+            .face
+            .as_ref()
+            .unwrap_or_else(|| panic!("label {:?} has no face", self.content));
+
+        let mirrored = matches!(side, Side::Underside);
+
+        let points = self
+            .text()
+            .contours()
+            .into_iter()
+            .flatten()
+            .map(|point| self.transform(point, mirrored))
+            .collect::<Vec<_>>();
+
+        let bounds = points
+            .iter()
+            .fold([[f64::MAX; 2], [f64::MIN; 2]], |[min, max], [x, y]| {
+                [
+                    [min[0].min(*x), min[1].min(*y)],
+                    [max[0].max(*x), max[1].max(*y)],
+                ]
+            });
+
+        let [min, max] = bounds;
+        let content = &self.content;
+
+        assert!(
+            corners(bounds)
+                .into_iter()
+                .all(|corner| outline.contains(corner, self.margin)),
+            "label {content:?} ({:.1}x{:.1}mm) doesn't fit on its face",
+            max[0] - min[0],
+            max[1] - min[1],
+        );
+
+        for region in &self.keep_out {
+            assert!(
+                !region.overlaps(bounds, self.margin),
+                "label {content:?} cuts into {region:?}"
+            );
+        }
+
+        let mut shape = self.text().into_object();
+
+        if mirrored {
+            shape = shape >> mirror_x();
+        }
+
+        let shape =
+            shape >> rotate_z(self.rotation) >> translate([self.center[0], self.center[1], 0.0]);
+
+        let overlap = 0.01;
+        let shape = shape >> linear_extrude(self.depth + overlap);
+
+        match side {
+            Side::Top => shape >> up(z - self.depth),
+            Side::Underside => shape >> up(z - overlap),
+        }
+    }
+
+    pub fn apply(&self, part: Object) -> Object {
+        if enabled() {
+            part - self.cut()
+        } else {
+            part
+        }
+    }
+}
+
+fn corners([min, max]: [[f64; 2]; 2]) -> [[f64; 2]; 4] {
+    [min, [max[0], min[1]], max, [min[0], max[1]]]
+}
+
+fn segment_distance(a: [f64; 2], b: [f64; 2], p: [f64; 2]) -> f64 {
+    let ab = [b[0] - a[0], b[1] - a[1]];
+    let ap = [p[0] - a[0], p[1] - a[1]];
+    let len = ab[0] * ab[0] + ab[1] * ab[1];
+    let t = if len == 0.0 {
+        0.0
+    } else {
+        ((ap[0] * ab[0] + ap[1] * ab[1]) / len).clamp(0.0, 1.0)
+    };
+    (a[0] + ab[0] * t - p[0]).hypot(a[1] + ab[1] * t - p[1])
+}
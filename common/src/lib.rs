@@ -0,0 +1,16 @@
+pub mod assets;
+pub mod dxf;
+pub mod font;
+pub mod label;
+pub mod mesh;
+pub mod printer;
+pub mod split;
+pub mod svg;
+
+pub use assets::asset;
+pub use dxf::dxf;
+pub use font::{font, text};
+pub use label::{label, Region};
+pub use printer::{printer, Printer};
+pub use split::{Joint, Split};
+pub use svg::{svg, Anchor};
@@ -0,0 +1,791 @@
+use crate::font::contains;
+use rsolid::*;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+/// Vertices closer than this are welded together
+const WELD: f64 = 1e-5;
+/// How far an outline's radius may wander, relative to its mean, and still count as a circle
+const ROUNDNESS: f64 = 0.05;
+
+/// A triangle mesh read from an STL file
+#[derive(Clone, Debug)]
+pub struct Mesh {
+    name: String,
+    vertices: Vec<[f64; 3]>,
+    triangles: Vec<[usize; 3]>,
+    /// Corners that were merged into a nearby vertex they didn't exactly match
+    welded: usize,
+}
+
+/// What `Mesh::repair` found and fixed
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    pub welded: usize,
+    pub degenerate: usize,
+    pub duplicate: usize,
+    pub flipped: usize,
+    pub inverted: usize,
+    pub patched: usize,
+    /// Edges shared by more than two triangles, which can't be repaired automatically
+    pub non_manifold: usize,
+}
+
+impl Report {
+    pub fn is_clean(&self) -> bool {
+        self.welded
+            + self.degenerate
+            + self.duplicate
+            + self.flipped
+            + self.inverted
+            + self.patched
+            + self.non_manifold
+            == 0
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "welded {} duplicate vertices, removed {} degenerate and {} duplicate triangles, \
+             flipped {} triangles and {} inside-out shells, patched {} holes, \
+             {} non-manifold edges left",
+            self.welded,
+            self.degenerate,
+            self.duplicate,
+            self.flipped,
+            self.inverted,
+            self.patched,
+            self.non_manifold
+        )
+    }
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn edges([a, b, c]: [usize; 3]) -> [(usize, usize); 3] {
+    [(a, b), (b, c), (c, a)]
+}
+
+impl Mesh {
+    /// Reads a binary or ASCII STL, welding corners that are at or within `WELD` of each other
+    /// into shared vertices
+    pub fn read(name: impl Into<String>, path: &Path) -> Self {
+        let display = path.display();
+        let data = fs::read(path).unwrap_or_else(|err| panic!("could not read {display}: {err}"));
+
+        let corners = if is_binary(&data) {
+            data[84..]
+                .chunks_exact(50)
+                .map(|facet| {
+                    [0, 1, 2].map(|corner| {
+                        [0, 1, 2].map(|axis| {
+                            let at = 12 + corner * 12 + axis * 4;
+                            f32::from_le_bytes(facet[at..at + 4].try_into().unwrap()) as f64
+                        })
+                    })
+                })
+                .collect::<Vec<_>>()
+        } else {
+            let text = String::from_utf8_lossy(&data);
+            let vertices = text
+                .lines()
+                .filter_map(|line| line.trim().strip_prefix("vertex"))
+                .map(|coords| {
+                    let coords = coords
+                        .split_whitespace()
+                        .map(|v| {
+                            v.parse::<f64>()
+                                .unwrap_or_else(|_| panic!("{display} has an invalid vertex {v:?}"))
+                        })
+                        .collect::<Vec<_>>();
+                    <[f64; 3]>::try_from(coords)
+                        .unwrap_or_else(|_| panic!("{display} has a vertex without 3 coordinates"))
+                })
+                .collect::<Vec<_>>();
+
+            assert!(
+                vertices.len() % 3 == 0,
+                "{display} has a facet without 3 vertices"
+            );
+
+            vertices
+                .chunks_exact(3)
+                .map(|facet| [facet[0], facet[1], facet[2]])
+                .collect()
+        };
+
+        assert!(!corners.is_empty(), "{display} has no triangles");
+
+        let mut mesh = Self {
+            name: name.into(),
+            vertices: vec![],
+            triangles: vec![],
+            welded: 0,
+        };
+
+        let mut index = HashMap::new();
+        for facet in corners {
+            let triangle = facet.map(|point| {
+                let key = point.map(|v| (v / WELD).round() as i64);
+                let idx = *index.entry(key).or_insert_with(|| {
+                    mesh.vertices.push(point);
+                    mesh.vertices.len() - 1
+                });
+                if mesh.vertices[idx] != point {
+                    mesh.welded += 1;
+                }
+                idx
+            });
+            mesh.triangles.push(triangle);
+        }
+
+        mesh
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Welds duplicate vertices, drops degenerate and repeated triangles, makes the winding
+    /// consistent and outward facing, and patches open holes
+    pub fn repair(&mut self) -> Report {
+        let mut report = Report {
+            welded: self.welded,
+            ..Default::default()
+        };
+
+        let mut seen = HashSet::new();
+        let vertices = &self.vertices;
+        self.triangles.retain(|&[a, b, c]| {
+            let normal = cross(sub(vertices[b], vertices[a]), sub(vertices[c], vertices[a]));
+            if a == b || b == c || c == a || dot(normal, normal) < WELD * WELD * WELD * WELD {
+                report.degenerate += 1;
+                return false;
+            }
+
+            let mut key = [a, b, c];
+            key.sort_unstable();
+            if !seen.insert(key) {
+                report.duplicate += 1;
+                return false;
+            }
+
+            true
+        });
+
+        // shells are only closed once patched, and an open shell's volume says nothing about
+        // which way it faces
+        report.flipped = self.orient();
+        report.patched = self.patch_holes();
+        report.inverted = self.turn_outward();
+        report.non_manifold = self
+            .edge_faces()
+            .values()
+            .filter(|faces| faces.len() > 2)
+            .count();
+
+        report
+    }
+
+    /// The triangles on each undirected edge
+    fn edge_faces(&self) -> HashMap<(usize, usize), Vec<usize>> {
+        let mut out: HashMap<_, Vec<_>> = HashMap::new();
+        for (idx, triangle) in self.triangles.iter().enumerate() {
+            for (a, b) in edges(*triangle) {
+                out.entry((a.min(b), a.max(b))).or_default().push(idx);
+            }
+        }
+        out
+    }
+
+    /// Groups triangles into shells that share manifold edges
+    fn shells(&self) -> Vec<Vec<usize>> {
+        let edge_faces = self.edge_faces();
+        let mut shell_of = vec![None; self.triangles.len()];
+        let mut shells = vec![];
+
+        for seed in 0..self.triangles.len() {
+            if shell_of[seed].is_some() {
+                continue;
+            }
+
+            let id = shells.len();
+            let mut shell = vec![seed];
+            shell_of[seed] = Some(id);
+            let mut next = 0;
+
+            while next < shell.len() {
+                let current = shell[next];
+                next += 1;
+
+                for (a, b) in edges(self.triangles[current]) {
+                    let faces = &edge_faces[&(a.min(b), a.max(b))];
+                    if faces.len() != 2 {
+                        continue;
+                    }
+                    for &other in faces {
+                        if shell_of[other].is_none() {
+                            shell_of[other] = Some(id);
+                            shell.push(other);
+                        }
+                    }
+                }
+            }
+
+            shells.push(shell);
+        }
+
+        shells
+    }
+
+    /// Flips triangles until neighbors walk their shared edges in opposite directions,
+    /// returning how many were flipped
+    fn orient(&mut self) -> usize {
+        let edge_faces = self.edge_faces();
+        let mut visited = vec![false; self.triangles.len()];
+        let mut flipped = 0;
+
+        for seed in 0..self.triangles.len() {
+            if visited[seed] {
+                continue;
+            }
+            visited[seed] = true;
+            let mut queue = vec![seed];
+
+            while let Some(current) = queue.pop() {
+                for (a, b) in edges(self.triangles[current]) {
+                    let faces = &edge_faces[&(a.min(b), a.max(b))];
+                    if faces.len() != 2 {
+                        continue;
+                    }
+                    for &other in faces {
+                        if visited[other] {
+                            continue;
+                        }
+                        visited[other] = true;
+
+                        // a consistent neighbor walks the shared edge from b to a
+                        if edges(self.triangles[other]).contains(&(a, b)) {
+                            self.triangles[other].swap(1, 2);
+                            flipped += 1;
+                        }
+                        queue.push(other);
+                    }
+                }
+            }
+        }
+
+        flipped
+    }
+
+    /// Flips shells with a negative volume so their normals point out, returning how many
+    fn turn_outward(&mut self) -> usize {
+        let mut inverted = 0;
+
+        for shell in self.shells() {
+            let volume: f64 = shell
+                .iter()
+                .map(|&idx| {
+                    let [a, b, c] = self.triangles[idx].map(|v| self.vertices[v]);
+                    dot(a, cross(b, c))
+                })
+                .sum();
+
+            if volume < 0.0 {
+                for idx in shell {
+                    self.triangles[idx].swap(1, 2);
+                }
+                inverted += 1;
+            }
+        }
+
+        inverted
+    }
+
+    /// Closes every loop of open edges with triangles, returning how many loops
+    fn patch_holes(&mut self) -> usize {
+        let edge_faces = self.edge_faces();
+
+        // patches walk each open edge backwards so they wind the same way as their neighbors,
+        // and start from the lowest vertex so the same mesh always gets the same patches
+        let mut next = BTreeMap::new();
+        for triangle in &self.triangles {
+            for (a, b) in edges(*triangle) {
+                if edge_faces[&(a.min(b), a.max(b))].len() == 1 {
+                    next.insert(b, a);
+                }
+            }
+        }
+
+        let mut patched = 0;
+
+        while let Some((&start, _)) = next.first_key_value() {
+            let mut hole = vec![start];
+            let mut current = start;
+            let mut closed = false;
+
+            while let Some(to) = next.remove(&current) {
+                if to == start {
+                    closed = true;
+                    break;
+                }
+                hole.push(to);
+                current = to;
+            }
+
+            // open chains come from non-manifold edges, which are reported instead
+            if !closed || hole.len() < 3 {
+                continue;
+            }
+
+            let patch = self.triangulate(&hole);
+            self.triangles.extend(patch);
+            patched += 1;
+        }
+
+        patched
+    }
+
+    /// Fills a loop of vertices by clipping ears, so holes in concave outlines are patched
+    /// without triangles folding back over each other
+    fn triangulate(&self, hole: &[usize]) -> Vec<[usize; 3]> {
+        let at = |v: usize| self.vertices[v];
+
+        // Newell's normal, which follows the loop's winding even when it isn't flat
+        let mut normal = [0.0; 3];
+        for (i, &a) in hole.iter().enumerate() {
+            let [p, q] = [at(a), at(hole[(i + 1) % hole.len()])];
+            normal[0] += (p[1] - q[1]) * (p[2] + q[2]);
+            normal[1] += (p[2] - q[2]) * (p[0] + q[0]);
+            normal[2] += (p[0] - q[0]) * (p[1] + q[1]);
+        }
+
+        // whether `p` is left of the edge from `a` to `b`, looking down the normal
+        let left = |a: usize, b: usize, p: usize| {
+            dot(cross(sub(at(b), at(a)), sub(at(p), at(a))), normal) >= 0.0
+        };
+
+        let mut remaining = hole.to_vec();
+        let mut out = vec![];
+
+        while remaining.len() > 3 {
+            let len = remaining.len();
+            let corner = |i: usize| {
+                [
+                    remaining[(i + len - 1) % len],
+                    remaining[i],
+                    remaining[(i + 1) % len],
+                ]
+            };
+
+            let ear = (0..len).find(|&i| {
+                let [a, b, c] = corner(i);
+                let convex = dot(cross(sub(at(b), at(a)), sub(at(c), at(b))), normal) > 0.0;
+                convex
+                    && remaining.iter().all(|&p| {
+                        [a, b, c].contains(&p) || !(left(a, b, p) && left(b, c, p) && left(c, a, p))
+                    })
+            });
+
+            // a loop that twists too much to have an ear is closed off from its first corner
+            let ear = ear.unwrap_or(0);
+            out.push(corner(ear));
+            remaining.remove(ear);
+        }
+
+        out.push([remaining[0], remaining[1], remaining[2]]);
+        out
+    }
+
+    pub fn bounds(&self) -> [[f64; 3]; 2] {
+        self.vertices
+            .iter()
+            .fold([[f64::MAX; 3], [f64::MIN; 3]], |[min, max], point| {
+                [
+                    [0, 1, 2].map(|i| min[i].min(point[i])),
+                    [0, 1, 2].map(|i| max[i].max(point[i])),
+                ]
+            })
+    }
+
+    pub fn size(&self) -> [f64; 3] {
+        let [min, max] = self.bounds();
+        [0, 1, 2].map(|i| max[i] - min[i])
+    }
+
+    /// The center of the enclosed volume
+    pub fn centroid(&self) -> [f64; 3] {
+        let mut volume = 0.0;
+        let mut moment = [0.0; 3];
+
+        for triangle in &self.triangles {
+            let [a, b, c] = triangle.map(|v| self.vertices[v]);
+            // each triangle forms a signed tetrahedron with the origin
+            let v = dot(a, cross(b, c)) / 6.0;
+            volume += v;
+            for i in 0..3 {
+                moment[i] += v * (a[i] + b[i] + c[i]) / 4.0;
+            }
+        }
+
+        moment.map(|m| m / volume)
+    }
+
+    /// The directions the surface spreads along, from most to least, with the standard deviation
+    /// along each
+    pub fn principal_axes(&self) -> [([f64; 3], f64); 3] {
+        let mut area = 0.0;
+        let mut mean = [0.0; 3];
+        let mut samples = vec![];
+
+        for triangle in &self.triangles {
+            let [a, b, c] = triangle.map(|v| self.vertices[v]);
+            let normal = cross(sub(b, a), sub(c, a));
+            let weight = dot(normal, normal).sqrt() * 0.5;
+            let center = [0, 1, 2].map(|i| (a[i] + b[i] + c[i]) / 3.0);
+            area += weight;
+            for i in 0..3 {
+                mean[i] += center[i] * weight;
+            }
+            samples.push((center, weight));
+        }
+        let mean = mean.map(|m| m / area);
+
+        let mut covariance = [[0.0; 3]; 3];
+        for (center, weight) in samples {
+            let d = sub(center, mean);
+            for i in 0..3 {
+                for j in 0..3 {
+                    covariance[i][j] += d[i] * d[j] * weight / area;
+                }
+            }
+        }
+
+        let (values, vectors) = eigen(covariance);
+        let mut axes = [0, 1, 2].map(|i| {
+            let axis = [vectors[0][i], vectors[1][i], vectors[2][i]];
+            (axis, values[i].max(0.0).sqrt())
+        });
+        axes.sort_by(|a, b| b.1.total_cmp(&a.1));
+        axes
+    }
+
+    /// The closed outlines where the plane at `z` cuts the mesh
+    pub fn section(&self, z: f64) -> Vec<Vec<[f64; 2]>> {
+        // nudge the plane off any vertex so every crossing is on an edge
+        let mut z = z;
+        while self.vertices.iter().any(|v| v[2] == z) {
+            z += WELD;
+        }
+
+        let mut segments = vec![];
+        for triangle in &self.triangles {
+            let crossings = edges(*triangle)
+                .into_iter()
+                .filter(|(a, b)| (self.vertices[*a][2] > z) != (self.vertices[*b][2] > z))
+                .map(|(a, b)| (a.min(b), a.max(b)))
+                .collect::<Vec<_>>();
+            if let [from, to] = crossings[..] {
+                segments.push([from, to]);
+            }
+        }
+
+        let point = |(a, b): (usize, usize)| {
+            let [a, b] = [self.vertices[a], self.vertices[b]];
+            let t = (z - a[2]) / (b[2] - a[2]);
+            [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t]
+        };
+
+        let mut by_edge: HashMap<_, Vec<usize>> = HashMap::new();
+        for (idx, [from, to]) in segments.iter().enumerate() {
+            by_edge.entry(*from).or_default().push(idx);
+            by_edge.entry(*to).or_default().push(idx);
+        }
+
+        let mut used = vec![false; segments.len()];
+        let mut loops = vec![];
+
+        for seed in 0..segments.len() {
+            if used[seed] {
+                continue;
+            }
+            used[seed] = true;
+
+            let [start, mut current] = segments[seed];
+            let mut outline = vec![point(start)];
+
+            while current != start {
+                outline.push(point(current));
+                let Some(&next) = by_edge[&current].iter().find(|idx| !used[**idx]) else {
+                    break;
+                };
+                used[next] = true;
+                let [a, b] = segments[next];
+                current = if a == current { b } else { a };
+            }
+
+            if outline.len() > 2 {
+                loops.push(outline);
+            }
+        }
+
+        loops
+    }
+
+    /// Moves the mesh so its bounding box is centered on the z axis and rests on z = 0,
+    /// returning the offset that was taken off every vertex
+    pub fn center(&mut self) -> [f64; 3] {
+        let [min, max] = self.bounds();
+        let shift = [(min[0] + max[0]) * 0.5, (min[1] + max[1]) * 0.5, min[2]];
+        for point in &mut self.vertices {
+            *point = sub(*point, shift);
+        }
+        shift
+    }
+
+    /// Writes the mesh as a binary STL
+    pub fn write(&self, path: &Path) {
+        let mut data = vec![0u8; 80];
+        data.extend((self.triangles.len() as u32).to_le_bytes());
+
+        for triangle in &self.triangles {
+            let [a, b, c] = triangle.map(|v| self.vertices[v]);
+            let normal = cross(sub(b, a), sub(c, a));
+            let len = dot(normal, normal).sqrt();
+            let normal = normal.map(|v| v / len);
+
+            for point in [normal, a, b, c] {
+                for v in point {
+                    data.extend((v as f32).to_le_bytes());
+                }
+            }
+            data.extend([0u8; 2]);
+        }
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .unwrap_or_else(|err| panic!("could not create {}: {err}", dir.display()));
+        }
+        fs::write(path, data)
+            .unwrap_or_else(|err| panic!("could not write {}: {err}", path.display()));
+    }
+
+    /// Writes the mesh next to the assets and imports it
+    pub fn into_object(self) -> Object {
+        let path = PathBuf::from(crate::assets::ASSETS_DIR)
+            .join("repaired")
+            .join(format!("{}.stl", self.name));
+        self.write(&path);
+        import::stl(path.display().to_string()).into()
+    }
+}
+
+/// The round holes in a section, as their center and diameter
+pub fn holes(section: &[Vec<[f64; 2]>]) -> Vec<([f64; 2], f64)> {
+    section
+        .iter()
+        .enumerate()
+        .filter(|(idx, outline)| {
+            // holes are outlines nested an odd number of times
+            let depth = section
+                .iter()
+                .enumerate()
+                .filter(|(other, outer)| other != idx && contains(outer, outline[0]))
+                .count();
+            depth % 2 == 1
+        })
+        .filter_map(|(_, outline)| {
+            let n = outline.len() as f64;
+            let center = [0, 1].map(|i| outline.iter().map(|p| p[i]).sum::<f64>() / n);
+            let radii = outline
+                .iter()
+                .map(|p| (p[0] - center[0]).hypot(p[1] - center[1]))
+                .collect::<Vec<_>>();
+            let mean = radii.iter().sum::<f64>() / n;
+            let spread = radii.iter().map(|r| (r - mean).abs()).fold(0.0, f64::max);
+
+            (spread <= mean * ROUNDNESS).then_some((center, mean * 2.0))
+        })
+        .collect()
+}
+
+/// Eigenvalues and column eigenvectors of a symmetric 3x3 matrix, by Jacobi rotations
+fn eigen(mut m: [[f64; 3]; 3]) -> ([f64; 3], [[f64; 3]; 3]) {
+    let mut v = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+    for _ in 0..50 {
+        let (p, q) = [(0, 1), (0, 2), (1, 2)]
+            .into_iter()
+            .max_by(|a, b| m[a.0][a.1].abs().total_cmp(&m[b.0][b.1].abs()))
+            .unwrap();
+        if m[p][q].abs() < 1e-12 {
+            break;
+        }
+
+        let theta = (m[q][q] - m[p][p]) / (2.0 * m[p][q]);
+        let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+        let t = if theta == 0.0 { 1.0 } else { t };
+        let c = 1.0 / (t * t + 1.0).sqrt();
+        let s = t * c;
+
+        for row in &mut m {
+            let (mp, mq) = (row[p], row[q]);
+            row[p] = c * mp - s * mq;
+            row[q] = s * mp + c * mq;
+        }
+        let (mp, mq) = (m[p], m[q]);
+        m[p] = [0, 1, 2].map(|k| c * mp[k] - s * mq[k]);
+        m[q] = [0, 1, 2].map(|k| s * mp[k] + c * mq[k]);
+        for row in &mut v {
+            let (vp, vq) = (row[p], row[q]);
+            row[p] = c * vp - s * vq;
+            row[q] = s * vp + c * vq;
+        }
+    }
+
+    ([m[0][0], m[1][1], m[2][2]], v)
+}
+
+fn is_binary(data: &[u8]) -> bool {
+    if data.len() < 84 {
+        return false;
+    }
+    let count = u32::from_le_bytes(data[80..84].try_into().unwrap()) as usize;
+    // ASCII files start with "solid" too, but only binary ones match their facet count
+    data.len() == 84 + count * 50
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mesh(vertices: Vec<[f64; 3]>, triangles: Vec<[usize; 3]>) -> Mesh {
+        Mesh {
+            name: "test".into(),
+            vertices,
+            triangles,
+            welded: 0,
+        }
+    }
+
+    /// A unit cube away from the origin, without its top face
+    fn open_cube() -> Mesh {
+        let vertices = (0..8)
+            .map(|i| [i & 1, (i >> 1) & 1, (i >> 2) & 1].map(|c| 10.0 + c as f64))
+            .collect();
+        let triangles = vec![
+            [0, 2, 3],
+            [0, 3, 1],
+            [0, 1, 5],
+            [0, 5, 4],
+            [2, 6, 7],
+            [2, 7, 3],
+            [0, 4, 6],
+            [0, 6, 2],
+            [1, 3, 7],
+            [1, 7, 5],
+        ];
+        mesh(vertices, triangles)
+    }
+
+    fn volume(mesh: &Mesh) -> f64 {
+        mesh.triangles
+            .iter()
+            .map(|triangle| {
+                let [a, b, c] = triangle.map(|v| mesh.vertices[v]);
+                dot(a, cross(b, c)) / 6.0
+            })
+            .sum()
+    }
+
+    fn is_closed(mesh: &Mesh) -> bool {
+        mesh.edge_faces().values().all(|faces| faces.len() == 2)
+    }
+
+    #[test]
+    fn patches_a_missing_face() {
+        let mut cube = open_cube();
+        let report = cube.repair();
+
+        assert_eq!(report.patched, 1);
+        assert_eq!(report.inverted, 0);
+        assert_eq!(cube.triangles.len(), 12);
+        assert!(is_closed(&cube));
+        assert!((volume(&cube) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn turns_an_inside_out_cube_once_it_is_patched() {
+        let mut cube = open_cube();
+        for triangle in &mut cube.triangles {
+            triangle.swap(1, 2);
+        }
+        let report = cube.repair();
+
+        assert_eq!(report.patched, 1);
+        assert_eq!(report.inverted, 1);
+        assert!(is_closed(&cube));
+        assert!((volume(&cube) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn patches_concave_holes_without_folding() {
+        // a U-shaped prism without its caps, starting the outline on an inside corner
+        let outline = [
+            [1.0, 1.0],
+            [1.0, 2.0],
+            [0.0, 2.0],
+            [0.0, 0.0],
+            [3.0, 0.0],
+            [3.0, 2.0],
+            [2.0, 2.0],
+            [2.0, 1.0],
+        ];
+        let len = outline.len();
+        let vertices = [0.0, 1.0]
+            .into_iter()
+            .flat_map(|z| outline.map(|[x, y]| [x, y, z]))
+            .collect();
+        let triangles = (0..len)
+            .flat_map(|i| {
+                let j = (i + 1) % len;
+                [[i, j, j + len], [i, j + len, i + len]]
+            })
+            .collect();
+        let mut prism = mesh(vertices, triangles);
+        let report = prism.repair();
+
+        assert_eq!(report.patched, 2);
+        assert!(is_closed(&prism));
+        assert!((volume(&prism) - 5.0).abs() < 1e-9);
+
+        for triangle in &prism.triangles[len * 2..] {
+            let [a, b, c] = triangle.map(|v| prism.vertices[v]);
+            let normal = cross(sub(b, a), sub(c, a));
+            let outward = if a[2] == 0.0 { -1.0 } else { 1.0 };
+            assert!(
+                normal[2] * outward > 0.0,
+                "patch {triangle:?} folds back over the cap"
+            );
+        }
+    }
+}
@@ -0,0 +1,54 @@
+pub const ENV: &str = "PRINTER";
+
+#[derive(Clone, Copy, Debug)]
+pub struct Printer {
+    pub name: &'static str,
+    /// The usable build plate, in millimeters
+    pub bed: [f64; 2],
+    /// Added to a hole's diameter so the part presses snugly onto a shaft of the nominal size
+    pub press_fit: f64,
+    /// Added to a hole's diameter so the part turns or slides freely
+    pub clearance: f64,
+}
+
+pub const PRINTERS: &[Printer] = &[
+    Printer {
+        name: "mk4",
+        bed: [250.0, 210.0],
+        press_fit: 0.1,
+        clearance: 0.3,
+    },
+    Printer {
+        name: "mini",
+        bed: [180.0, 180.0],
+        press_fit: 0.1,
+        clearance: 0.3,
+    },
+    Printer {
+        name: "a1-mini",
+        bed: [180.0, 180.0],
+        press_fit: 0.05,
+        clearance: 0.25,
+    },
+    Printer {
+        name: "x1",
+        bed: [256.0, 256.0],
+        press_fit: 0.05,
+        clearance: 0.25,
+    },
+];
+
+/// The profile named by `PRINTER`, or the first one when it isn't set.
+pub fn printer() -> &'static Printer {
+    let Some(name) = std::env::var(ENV).ok().filter(|v| !v.is_empty()) else {
+        return &PRINTERS[0];
+    };
+
+    PRINTERS
+        .iter()
+        .find(|printer| printer.name.eq_ignore_ascii_case(name.trim()))
+        .unwrap_or_else(|| {
+            let available = PRINTERS.iter().map(|p| p.name).collect::<Vec<_>>();
+            panic!("unknown printer {name:?} (available: {available:?})")
+        })
+}
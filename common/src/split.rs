@@ -0,0 +1,550 @@
+use crate::printer;
+use rsolid::*;
+
+/// Big enough to cover any part on the bed
+const EXTENT: f64 = 2000.0;
+const OVERLAP: f64 = 0.01;
+
+#[derive(Clone, Copy, Debug)]
+enum Cut {
+    /// Equal wedges around the z axis, with the first seam at `start` degrees from +x
+    Wedges { count: u32, start: f64 },
+    /// Two halves either side of a line at `angle` degrees from +x, `offset` from the origin
+    Plane { angle: f64, offset: f64 },
+}
+
+/// An alignment feature repeated along every seam
+#[derive(Clone, Copy, Debug)]
+pub enum Joint {
+    /// A tail on one side that drops into a socket on the other, through the whole seam height
+    Dovetail { width: f64, depth: f64 },
+    /// A pin on one side that presses into a hole on the other
+    Pin { d: f64, length: f64 },
+    /// A threaded hole on one side and a clearance hole on the other, for a screw or threaded rod
+    Screw { size: f64, pitch: f64, length: f64 },
+}
+
+/// A solid a joint adds to or cuts from a piece, in the frame of a seam running along +x
+#[derive(Clone, Copy, Debug)]
+enum Shape {
+    /// A quad extruded from `z[0]` to `z[1]`
+    Prism { points: [[f64; 2]; 4], z: [f64; 2] },
+    /// A round bar of radius `r` along +y from `y[0]` to `y[1]`, its axis at `x` and `z`
+    Rod { x: f64, z: f64, r: f64, y: [f64; 2] },
+    /// A threaded rod along +y from `y[0]` to `y[1]`, its axis at `x` and `z`
+    Thread {
+        x: f64,
+        z: f64,
+        size: f64,
+        pitch: f64,
+        y: [f64; 2],
+    },
+}
+
+impl Shape {
+    fn object(&self) -> Object {
+        // rods are stood on z = 0 and laid down towards +y; the screw hangs below z = 0 until
+        // it's lifted
+        match *self {
+            Self::Prism {
+                points,
+                z: [z0, z1],
+            } => polygon(points) >> linear_extrude(z1 - z0) >> up(z0),
+            Self::Rod {
+                x,
+                z,
+                r,
+                y: [y0, y1],
+            } => cylinder(y1 - y0, r) >> rotate_x(-90) >> translate([x, y0, z]),
+            Self::Thread {
+                x,
+                z,
+                size,
+                pitch,
+                y: [y0, y1],
+            } => {
+                let len = y1 - y0;
+                let screw: Object = bosl::metric_screws::screw()
+                    .head_len(0)
+                    .len(len)
+                    .size(size)
+                    .pitch(pitch)
+                    .into();
+                screw >> up(len) >> rotate_x(-90) >> translate([x, y0, z])
+            }
+        }
+    }
+
+    /// The box the `object` fills
+    #[cfg(test)]
+    fn bounds(&self) -> [[f64; 3]; 2] {
+        let rod =
+            |x: f64, z: f64, r: f64, [y0, y1]: [f64; 2]| [[x - r, y0, z - r], [x + r, y1, z + r]];
+
+        match *self {
+            Self::Prism {
+                points,
+                z: [z0, z1],
+            } => {
+                let [x, y] = [0, 1].map(|i| points.map(|point| point[i]));
+                let min = |v: [f64; 4]| v.into_iter().fold(f64::INFINITY, f64::min);
+                let max = |v: [f64; 4]| v.into_iter().fold(f64::NEG_INFINITY, f64::max);
+                [[min(x), min(y), z0], [max(x), max(y), z1]]
+            }
+            Self::Rod { x, z, r, y } => rod(x, z, r, y),
+            Self::Thread { x, z, size, y, .. } => rod(x, z, size * 0.5, y),
+        }
+    }
+}
+
+impl Joint {
+    /// What the first side gains, what it loses and what the second side loses at `x` along a
+    /// seam running along +x, with the second side towards +y
+    fn features(&self, x: f64, [z0, z1]: [f64; 2]) -> [Option<Shape>; 3] {
+        let clearance = printer().clearance;
+        let z = (z0 + z1) * 0.5;
+
+        match *self {
+            Self::Dovetail { width, depth } => {
+                let tail = |grow: f64| Shape::Prism {
+                    points: [
+                        [x - width * 0.5 - grow, -OVERLAP],
+                        [x + width * 0.5 + grow, -OVERLAP],
+                        [x + width * 0.75 + grow, depth + grow],
+                        [x - width * 0.75 - grow, depth + grow],
+                    ],
+                    z: [z0 - OVERLAP, z1 + OVERLAP],
+                };
+                [Some(tail(0.0)), None, Some(tail(clearance * 0.5))]
+            }
+            Self::Pin { d, length } => {
+                let pin = |d: f64, length: f64| Shape::Rod {
+                    x,
+                    z,
+                    r: d * 0.5,
+                    y: [-length * 0.5, length * 0.5],
+                };
+                [
+                    Some(pin(d, length)),
+                    None,
+                    Some(pin(d + clearance, length + clearance * 2.0)),
+                ]
+            }
+            Self::Screw {
+                size,
+                pitch,
+                length,
+            } => {
+                // the clear hole is towards -y and the threaded one towards +y
+                let half = length * 0.5;
+                let clear = Shape::Rod {
+                    x,
+                    z,
+                    r: (size + clearance) * 0.5,
+                    y: [-half, OVERLAP],
+                };
+                let threaded = Shape::Thread {
+                    x,
+                    z,
+                    size,
+                    pitch,
+                    y: [0.0, half],
+                };
+                [None, Some(clear), Some(threaded)]
+            }
+        }
+    }
+}
+
+/// A joint feature added to or cut from one piece, with the seam it sits on
+#[derive(Clone, Copy, Debug)]
+struct Change {
+    piece: usize,
+    cut: bool,
+    shape: Shape,
+    angle: f64,
+    offset: f64,
+}
+
+impl Change {
+    fn object(&self) -> Object {
+        self.shape.object() >> back(self.offset) >> rotate_z(self.angle)
+    }
+}
+
+/// Cuts a part that is too big for the bed into pieces with alignment features at the seams
+#[derive(Clone, Debug)]
+pub struct Split {
+    cut: Cut,
+    joint: Option<Joint>,
+    positions: Vec<f64>,
+    z: [f64; 2],
+}
+
+/// Splits into `count` equal wedges around the z axis
+pub fn wedges(count: u32) -> Split {
+    assert!(count > 0, "a part can't be split into 0 wedges");
+
+    Split {
+        cut: Cut::Wedges { count, start: 0.0 },
+        joint: None,
+        positions: vec![],
+        z: [0.0, 1.0],
+    }
+}
+
+/// Splits in two along a line through the origin at `angle` degrees from +x
+pub fn plane(angle: f64) -> Split {
+    Split {
+        cut: Cut::Plane { angle, offset: 0.0 },
+        joint: None,
+        positions: vec![],
+        z: [0.0, 1.0],
+    }
+}
+
+/// The fewest wedges a disc of `radius` splits into so every wedge fits on `bed`
+pub fn wedges_to_fit(radius: f64, bed: [f64; 2]) -> u32 {
+    let fits = |[w, h]: [f64; 2]| (w <= bed[0] && h <= bed[1]) || (w <= bed[1] && h <= bed[0]);
+
+    if fits([radius * 2.0; 2]) {
+        return 1;
+    }
+
+    (2..=12)
+        .find(|count| {
+            let half = (std::f64::consts::PI / *count as f64).min(std::f64::consts::FRAC_PI_2);
+            fits([radius * 2.0 * half.sin(), radius])
+        })
+        .unwrap_or_else(|| {
+            panic!(
+                "a {radius:.0}mm radius part doesn't fit a {}x{}mm bed in any number of wedges",
+                bed[0], bed[1]
+            )
+        })
+}
+
+impl Split {
+    /// Turns the wedges so the first seam is at `degrees` from +x
+    pub fn start(mut self, degrees: f64) -> Self {
+        match &mut self.cut {
+            Cut::Wedges { start, .. } => *start = degrees,
+            Cut::Plane { .. } => panic!("only wedges have a start angle"),
+        }
+        self
+    }
+
+    /// Moves a plane cut away from the origin, towards the second half
+    pub fn offset(mut self, distance: f64) -> Self {
+        match &mut self.cut {
+            Cut::Plane { offset, .. } => *offset = distance,
+            Cut::Wedges { .. } => panic!("only plane cuts have an offset"),
+        }
+        self
+    }
+
+    pub fn joint(mut self, joint: Joint) -> Self {
+        self.joint = Some(joint);
+        self
+    }
+
+    /// Where joints go along each seam, measured from the origin for wedges and from the
+    /// point nearest the origin for plane cuts
+    pub fn at(mut self, positions: &[f64]) -> Self {
+        self.positions = positions.to_vec();
+        self
+    }
+
+    /// The height the joints span. Dovetails fill it and pins and screws sit in its middle.
+    pub fn z(mut self, z: [f64; 2]) -> Self {
+        self.z = z;
+        self
+    }
+
+    pub fn pieces(&self) -> usize {
+        match self.cut {
+            Cut::Wedges { count, .. } => count as usize,
+            Cut::Plane { .. } => 2,
+        }
+    }
+
+    /// The part of `outline` that falls in piece `idx`
+    fn outline(&self, idx: usize, outline: Vec<[f64; 2]>) -> Vec<[f64; 2]> {
+        self.sides(idx)
+            .into_iter()
+            .fold(outline, |outline, side| clip(&outline, side))
+    }
+
+    fn masks(&self) -> Vec<Object> {
+        let bed = [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]];
+        let bed = bed.map(|[x, y]| [x * EXTENT, y * EXTENT]).to_vec();
+
+        (0..self.pieces())
+            .map(|idx| {
+                polygon(self.outline(idx, bed.clone())) >> linear_extrude(EXTENT).center(true)
+            })
+            .collect()
+    }
+
+    /// Each seam's angle, its distance from the origin, and the pieces on either side, with the
+    /// second piece counterclockwise of the seam, on the +y side before the seam is turned
+    fn seams(&self) -> Vec<(f64, f64, [usize; 2])> {
+        match self.cut {
+            Cut::Wedges { count: 1, .. } => vec![],
+            Cut::Wedges { count, start } => {
+                let count = count as usize;
+                let step = 360.0 / count as f64;
+                (0..count)
+                    .map(|i| (start + i as f64 * step, 0.0, [(i + count - 1) % count, i]))
+                    .collect()
+            }
+            Cut::Plane { angle, offset } => vec![(angle, offset, [1, 0])],
+        }
+    }
+
+    /// The half-planes bounding piece `idx`, as `(normal, distance)` with the piece where
+    /// `normal · p + distance >= 0`
+    fn sides(&self, idx: usize) -> Vec<([f64; 2], f64)> {
+        // the normal towards the counterclockwise side of a line at `degrees`
+        let normal = |degrees: f64| {
+            let (sin, cos) = f64::to_radians(degrees).sin_cos();
+            [-sin, cos]
+        };
+        let flip = |[x, y]: [f64; 2]| [-x, -y];
+
+        match self.cut {
+            Cut::Wedges { count: 1, .. } => vec![],
+            Cut::Wedges { count, start } => {
+                let step = 360.0 / count as f64;
+                let from = start + idx as f64 * step;
+                vec![(normal(from), 0.0), (flip(normal(from + step)), 0.0)]
+            }
+            Cut::Plane { angle, offset } if idx == 0 => vec![(normal(angle), offset)],
+            Cut::Plane { angle, offset } => vec![(flip(normal(angle)), -offset)],
+        }
+    }
+
+    /// The joint features each piece gains and loses, seam by seam
+    fn changes(&self) -> Vec<Change> {
+        let Some(joint) = self.joint else {
+            return vec![];
+        };
+        let mut changes = vec![];
+
+        for (angle, offset, [a, b]) in self.seams() {
+            for x in &self.positions {
+                let [gain, loss, socket] = joint.features(*x, self.z);
+                let placed = [(a, false, gain), (a, true, loss), (b, true, socket)];
+
+                for (piece, cut, shape) in placed {
+                    if let Some(shape) = shape {
+                        changes.push(Change {
+                            piece,
+                            cut,
+                            shape,
+                            angle,
+                            offset,
+                        });
+                    }
+                }
+            }
+        }
+
+        changes
+    }
+
+    /// Cuts `part` into its pieces, in counterclockwise order
+    pub fn apply(&self, part: &Object) -> Vec<Object> {
+        if self.pieces() == 1 {
+            return vec![part.clone()];
+        }
+
+        let mut pieces = self
+            .masks()
+            .into_iter()
+            .map(|mask| part.clone() & mask)
+            .collect::<Vec<_>>();
+
+        for change in self.changes() {
+            if change.cut {
+                pieces[change.piece] -= change.object();
+            } else {
+                pieces[change.piece] += change.object();
+            }
+        }
+
+        pieces
+    }
+}
+
+/// The part of a convex `outline` on the inner side of a half-plane
+fn clip(outline: &[[f64; 2]], (normal, distance): ([f64; 2], f64)) -> Vec<[f64; 2]> {
+    let side = |[x, y]: [f64; 2]| normal[0] * x + normal[1] * y + distance;
+    let mut out = vec![];
+
+    for (i, &from) in outline.iter().enumerate() {
+        let to = outline[(i + 1) % outline.len()];
+        let [a, b] = [side(from), side(to)];
+
+        if a >= 0.0 {
+            out.push(from);
+        }
+        if (a >= 0.0) != (b >= 0.0) {
+            let t = a / (a - b);
+            out.push([
+                from[0] + (to[0] - from[0]) * t,
+                from[1] + (to[1] - from[1]) * t,
+            ]);
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Bounds = [[f64; 3]; 2];
+
+    const PART: Bounds = [[-50.0, -40.0, 0.0], [50.0, 40.0, 10.0]];
+
+    /// Grows `bounds` to take in `points`
+    fn enclose(
+        bounds: Option<Bounds>,
+        points: impl IntoIterator<Item = [f64; 3]>,
+    ) -> Option<Bounds> {
+        points.into_iter().fold(bounds, |bounds, point| {
+            let [min, max] = bounds.unwrap_or([point; 2]);
+            Some([
+                [0, 1, 2].map(|i| min[i].min(point[i])),
+                [0, 1, 2].map(|i| max[i].max(point[i])),
+            ])
+        })
+    }
+
+    /// The box a change fills once it's moved to its seam
+    fn placed(change: &Change) -> Bounds {
+        let [min, max] = change.shape.bounds();
+        let (sin, cos) = change.angle.to_radians().sin_cos();
+        let mut corners = vec![];
+
+        for x in [min[0], max[0]] {
+            for y in [min[1] - change.offset, max[1] - change.offset] {
+                for z in [min[2], max[2]] {
+                    corners.push([x * cos - y * sin, x * sin + y * cos, z]);
+                }
+            }
+        }
+
+        enclose(None, corners).unwrap()
+    }
+
+    /// What piece `idx` of `PART` keeps of it, before any joints
+    fn cut(split: &Split, idx: usize) -> Option<Bounds> {
+        let [min, max] = PART;
+        let outline = vec![
+            [min[0], min[1]],
+            [max[0], min[1]],
+            [max[0], max[1]],
+            [min[0], max[1]],
+        ];
+        let points = split
+            .outline(idx, outline)
+            .into_iter()
+            .flat_map(|[x, y]| [[x, y, min[2]], [x, y, max[2]]]);
+        enclose(None, points)
+    }
+
+    /// Piece `idx` of `PART` with everything `apply` adds to it
+    fn piece(split: &Split, idx: usize) -> Option<Bounds> {
+        let gains = split
+            .changes()
+            .into_iter()
+            .filter(|c| c.piece == idx && !c.cut);
+        gains.fold(cut(split, idx), |bounds, change| {
+            let [min, max] = placed(&change);
+            enclose(bounds, [min, max])
+        })
+    }
+
+    fn assert_near(actual: Option<Bounds>, expected: Bounds) {
+        let actual = actual.expect("the piece is empty");
+        let near = (0..2).all(|i| (0..3).all(|j| (actual[i][j] - expected[i][j]).abs() < 1e-6));
+        assert!(near, "expected {expected:?}, got {actual:?}");
+    }
+
+    #[test]
+    fn wedges_share_the_part_between_them() {
+        let split = wedges(4);
+
+        assert_near(cut(&split, 0), [[0.0, 0.0, 0.0], [50.0, 40.0, 10.0]]);
+        assert_near(cut(&split, 1), [[-50.0, 0.0, 0.0], [0.0, 40.0, 10.0]]);
+        assert_near(cut(&split, 2), [[-50.0, -40.0, 0.0], [0.0, 0.0, 10.0]]);
+        assert_near(cut(&split, 3), [[0.0, -40.0, 0.0], [50.0, 0.0, 10.0]]);
+    }
+
+    #[test]
+    fn dovetails_reach_across_a_plane_seam() {
+        let split = plane(0.0)
+            .offset(10.0)
+            .joint(Joint::Dovetail {
+                width: 10.0,
+                depth: 5.0,
+            })
+            .at(&[0.0])
+            .z([0.0, 10.0]);
+
+        // the first piece is the +y side, and the tail of the second reaches into it
+        assert_near(piece(&split, 0), [[-50.0, -10.0, 0.0], [50.0, 40.0, 10.0]]);
+        assert_near(
+            piece(&split, 1),
+            [[-50.0, -40.0, -OVERLAP], [50.0, -5.0, 10.0 + OVERLAP]],
+        );
+    }
+
+    #[test]
+    fn dovetails_turn_with_the_seam() {
+        let split = plane(90.0)
+            .joint(Joint::Dovetail {
+                width: 10.0,
+                depth: 5.0,
+            })
+            .at(&[0.0])
+            .z([0.0, 10.0]);
+
+        assert_near(piece(&split, 0), [[-50.0, -40.0, 0.0], [0.0, 40.0, 10.0]]);
+        assert_near(
+            piece(&split, 1),
+            [[-5.0, -40.0, -OVERLAP], [50.0, 40.0, 10.0 + OVERLAP]],
+        );
+    }
+
+    #[test]
+    fn screw_holes_land_in_their_own_pieces() {
+        let split = plane(0.0)
+            .offset(10.0)
+            .joint(Joint::Screw {
+                size: 3.0,
+                pitch: 0.5,
+                length: 16.0,
+            })
+            .at(&[0.0])
+            .z([0.0, 10.0]);
+
+        assert_near(piece(&split, 0), [[-50.0, -10.0, 0.0], [50.0, 40.0, 10.0]]);
+        assert_near(piece(&split, 1), [[-50.0, -40.0, 0.0], [50.0, -10.0, 10.0]]);
+
+        let holes = split.changes();
+        assert_eq!(holes.len(), 2, "a screw makes two holes");
+
+        for hole in &holes {
+            assert!(hole.cut, "a screw joint adds nothing");
+
+            let [min, max] = placed(hole);
+            let [lo, hi] = cut(&split, hole.piece).unwrap();
+            let inside = (0..3).all(|i| min[i] >= lo[i] - OVERLAP && max[i] <= hi[i] + OVERLAP);
+            assert!(inside, "the {:?} misses piece {}", hole.shape, hole.piece);
+        }
+    }
+}
@@ -0,0 +1,906 @@
+use rsolid::*;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const MM_PER_IN: f64 = 25.4;
+const CURVE_STEPS: usize = 8;
+/// Points sampled around a full turn of a circle or arc
+const TURN_STEPS: usize = 32;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Anchor {
+    Center,
+    BottomLeft,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Fit {
+    Width(f64),
+    Height(f64),
+    Within([f64; 2]),
+}
+
+pub struct Svg {
+    path: PathBuf,
+    view_box: [f64; 4],
+    size: [f64; 2],
+    outlines: Vec<Vec<[f64; 2]>>,
+    fit: Option<Fit>,
+    anchor: Anchor,
+    trim: bool,
+}
+
+/// Reads an SVG document and checks its viewBox and units so the artwork can be placed in
+/// millimeters instead of compensating for whatever the exporting tool did.
+pub fn svg(path: impl Into<PathBuf>) -> Svg {
+    let path = path.into();
+    let display = path.display();
+
+    let data =
+        fs::read_to_string(&path).unwrap_or_else(|err| panic!("could not read {display}: {err}"));
+
+    let tags = tags(&data);
+    let root = tags
+        .iter()
+        .find(|tag| tag.name == "svg" && tag.kind != TagKind::Close)
+        .unwrap_or_else(|| panic!("{display} has no <svg> element"));
+    let attr = |name: &str| root.attr(name);
+
+    let view_box = attr("viewBox").map(|value| {
+        let numbers = value
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|n| !n.is_empty())
+            .map(|n| {
+                n.parse::<f64>()
+                    .unwrap_or_else(|_| panic!("{display} has an invalid viewBox {value:?}"))
+            })
+            .collect::<Vec<_>>();
+
+        let view_box: [f64; 4] = numbers
+            .try_into()
+            .unwrap_or_else(|_| panic!("{display} has an invalid viewBox {value:?}"));
+
+        assert!(
+            view_box[2] > 0.0 && view_box[3] > 0.0,
+            "{display} has an empty viewBox {value:?}"
+        );
+
+        view_box
+    });
+
+    let width = attr("width").map(|value| length(value, &path));
+    let height = attr("height").map(|value| length(value, &path));
+
+    // without a width and height the viewBox is measured in px, like OpenSCAD does
+    let px = |user: f64| user * MM_PER_IN / 96.0;
+
+    let (view_box, size) = match (view_box, width, height) {
+        (Some(view_box), Some(width), Some(height)) => {
+            let [_, _, w, h] = view_box;
+            let (sx, sy) = (width / w, height / h);
+            assert!(
+                ((sx - sy) / sx).abs() < 1e-3,
+                "{display} stretches its viewBox unevenly ({w}x{h} into {width:.2}x{height:.2}mm)"
+            );
+            (view_box, [width, height])
+        }
+        (Some(view_box), Some(width), None) => {
+            let [_, _, w, h] = view_box;
+            (view_box, [width, h * width / w])
+        }
+        (Some(view_box), None, Some(height)) => {
+            let [_, _, w, h] = view_box;
+            (view_box, [w * height / h, height])
+        }
+        (Some(view_box), None, None) => (view_box, [px(view_box[2]), px(view_box[3])]),
+        (None, Some(width), Some(height)) => (
+            [0.0, 0.0, width / px(1.0), height / px(1.0)],
+            [width, height],
+        ),
+        (None, _, _) => panic!("{display} needs a viewBox or both a width and a height"),
+    };
+
+    // OpenSCAD flips the y axis so the bottom-left corner of the viewBox lands on the origin
+    let [min_x, min_y, w, h] = view_box;
+    let to_mm = |[x, y]: [f64; 2]| [(x - min_x) * size[0] / w, (min_y + h - y) * size[1] / h];
+    let outlines = artwork_outlines(&tags, &path)
+        .into_iter()
+        .map(|outline| outline.into_iter().map(to_mm).collect())
+        .collect();
+
+    Svg {
+        path,
+        view_box,
+        size,
+        outlines,
+        fit: None,
+        anchor: Anchor::BottomLeft,
+        trim: false,
+    }
+}
+
+impl Svg {
+    /// The document's user units, as `[min_x, min_y, width, height]`
+    pub fn view_box(&self) -> [f64; 4] {
+        self.view_box
+    }
+
+    /// The document size in millimeters, before any fitting
+    pub fn size(&self) -> [f64; 2] {
+        self.size
+    }
+
+    /// The bounds of the drawn artwork in millimeters, relative to the document's bottom-left corner
+    pub fn bounds(&self) -> [[f64; 2]; 2] {
+        let mut points = self.outlines.iter().flatten();
+        let first = *points
+            .next()
+            .unwrap_or_else(|| panic!("{} doesn't draw anything", self.path.display()));
+
+        points.fold([first; 2], |[min, max], [x, y]| {
+            [
+                [min[0].min(*x), min[1].min(*y)],
+                [max[0].max(*x), max[1].max(*y)],
+            ]
+        })
+    }
+
+    /// The outline of each drawn shape and subpath in millimeters, in the same frame as `bounds`
+    pub fn outlines(&self) -> &[Vec<[f64; 2]>] {
+        &self.outlines
+    }
+
+    /// Fits and anchors the drawn artwork instead of the whole document, ignoring any margin
+    /// the exporting tool left around it
+    pub fn trim(mut self) -> Self {
+        self.trim = true;
+        self
+    }
+
+    fn frame(&self) -> [[f64; 2]; 2] {
+        if self.trim {
+            self.bounds()
+        } else {
+            [[0.0; 2], self.size]
+        }
+    }
+
+    /// Scales the document to the given width, keeping its aspect ratio
+    pub fn width(mut self, width: f64) -> Self {
+        self.fit = Some(Fit::Width(width));
+        self
+    }
+
+    /// Scales the document to the given height, keeping its aspect ratio
+    pub fn height(mut self, height: f64) -> Self {
+        self.fit = Some(Fit::Height(height));
+        self
+    }
+
+    /// Scales the document to the largest size that fits inside `size`, keeping its aspect ratio
+    pub fn fit(mut self, size: [f64; 2]) -> Self {
+        self.fit = Some(Fit::Within(size));
+        self
+    }
+
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    pub fn center(self) -> Self {
+        self.anchor(Anchor::Center)
+    }
+
+    /// The scale applied to the document's millimeter size
+    pub fn scale(&self) -> f64 {
+        let [min, max] = self.frame();
+        let [w, h] = [max[0] - min[0], max[1] - min[1]];
+        let scale = match self.fit {
+            None => 1.0,
+            Some(Fit::Width(width)) => width / w,
+            Some(Fit::Height(height)) => height / h,
+            Some(Fit::Within([width, height])) => (width / w).min(height / h),
+        };
+
+        assert!(
+            scale.is_finite() && scale > 0.0,
+            "{} can't be fit to {:?}",
+            self.path.display(),
+            self.fit
+        );
+
+        scale
+    }
+
+    /// The size of the placed document, or of the artwork when trimmed, in millimeters
+    pub fn fitted_size(&self) -> [f64; 2] {
+        let scale = self.scale();
+        let [min, max] = self.frame();
+        [(max[0] - min[0]) * scale, (max[1] - min[1]) * scale]
+    }
+
+    pub fn into_object(self) -> Object<2> {
+        let factor = self.scale();
+        let [min, max] = self.frame();
+
+        let [x, y] = match self.anchor {
+            Anchor::Center => [(min[0] + max[0]) * 0.5, (min[1] + max[1]) * 0.5],
+            Anchor::BottomLeft => min,
+        };
+
+        let shape = import::svg(self.path.display().to_string()) >> translate([-x, -y, 0.0]);
+
+        shape >> scale([factor, factor, 1.0])
+    }
+}
+
+impl From<Svg> for Object<2> {
+    fn from(svg: Svg) -> Self {
+        svg.into_object()
+    }
+}
+
+/// Converts an SVG length to millimeters
+fn length(value: &str, path: &Path) -> f64 {
+    let split = value
+        .find(|c: char| c.is_ascii_alphabetic() || c == '%')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+
+    let number = number
+        .trim()
+        .parse::<f64>()
+        .unwrap_or_else(|_| panic!("{} has an invalid length {value:?}", path.display()));
+
+    let per_unit = match unit.trim() {
+        "mm" => 1.0,
+        "cm" => 10.0,
+        "in" => MM_PER_IN,
+        "pt" => MM_PER_IN / 72.0,
+        "pc" => MM_PER_IN / 6.0,
+        "" | "px" => MM_PER_IN / 96.0,
+        unit => panic!(
+            "{} uses unsupported unit {unit:?} in {value:?}",
+            path.display()
+        ),
+    };
+
+    number * per_unit
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum TagKind {
+    Open,
+    Close,
+    Empty,
+}
+
+struct Tag<'a> {
+    name: &'a str,
+    kind: TagKind,
+    attrs: Vec<(&'a str, &'a str)>,
+}
+
+impl Tag<'_> {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.trim())
+    }
+}
+
+/// Splits a document into its element tags, skipping comments, declarations and text
+fn tags(data: &str) -> Vec<Tag<'_>> {
+    let mut out = vec![];
+    let mut rest = data;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+
+        let skip = [("<!--", "-->"), ("<?", "?>"), ("<!", ">")]
+            .into_iter()
+            .find(|(open, _)| rest.starts_with(open));
+
+        if let Some((_, close)) = skip {
+            let Some(end) = rest.find(close) else { break };
+            rest = &rest[end + close.len()..];
+            continue;
+        }
+
+        let Some(end) = rest.find('>') else { break };
+        let body = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        let (kind, body) = if let Some(body) = body.strip_prefix('/') {
+            (TagKind::Close, body)
+        } else if let Some(body) = body.strip_suffix('/') {
+            (TagKind::Empty, body)
+        } else {
+            (TagKind::Open, body)
+        };
+
+        let split = body.find(char::is_whitespace).unwrap_or(body.len());
+        let (name, mut attrs_str) = body.split_at(split);
+        let mut attrs = vec![];
+
+        while let Some(eq) = attrs_str.find('=') {
+            let key = attrs_str[..eq].trim();
+            let value = attrs_str[eq + 1..].trim_start();
+            let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+                break;
+            };
+            let Some(end) = value[1..].find(quote) else {
+                break;
+            };
+            attrs.push((key, &value[1..end + 1]));
+            attrs_str = &value[end + 2..];
+        }
+
+        out.push(Tag {
+            name: name.trim(),
+            kind,
+            attrs,
+        });
+    }
+
+    out
+}
+
+/// An affine transform `[a, b, c, d, e, f]`, mapping `(x, y)` to `(ax + cy + e, bx + dy + f)`
+type Matrix = [f64; 6];
+
+const IDENTITY: Matrix = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+/// Returns the transform that applies `inner` and then `outer`
+fn multiply(outer: Matrix, inner: Matrix) -> Matrix {
+    let [a, b, c, d, e, f] = outer;
+    let [a2, b2, c2, d2, e2, f2] = inner;
+    [
+        a * a2 + c * b2,
+        b * a2 + d * b2,
+        a * c2 + c * d2,
+        b * c2 + d * d2,
+        a * e2 + c * f2 + e,
+        b * e2 + d * f2 + f,
+    ]
+}
+
+fn apply([a, b, c, d, e, f]: Matrix, [x, y]: [f64; 2]) -> [f64; 2] {
+    [a * x + c * y + e, b * x + d * y + f]
+}
+
+fn numbers(value: &str) -> Vec<f64> {
+    let mut out = vec![];
+    let bytes = value.as_bytes();
+    let mut idx = 0;
+
+    while idx < bytes.len() {
+        let c = bytes[idx];
+        if !(c.is_ascii_digit() || c == b'-' || c == b'+' || c == b'.') {
+            idx += 1;
+            continue;
+        }
+
+        let start = idx;
+        let mut seen_dot = false;
+        let mut seen_exp = false;
+        idx += 1;
+        if c == b'.' {
+            seen_dot = true;
+        }
+
+        while idx < bytes.len() {
+            let c = bytes[idx];
+            let prev = bytes[idx - 1];
+            if c.is_ascii_digit() {
+                idx += 1;
+            } else if c == b'.' && !seen_dot && !seen_exp {
+                seen_dot = true;
+                idx += 1;
+            } else if (c == b'e' || c == b'E') && !seen_exp {
+                seen_exp = true;
+                idx += 1;
+            } else if (c == b'-' || c == b'+') && (prev == b'e' || prev == b'E') {
+                idx += 1;
+            } else {
+                break;
+            }
+        }
+
+        if let Ok(number) = value[start..idx].parse() {
+            out.push(number);
+        }
+    }
+
+    out
+}
+
+fn transform(value: &str, path: &Path) -> Matrix {
+    let mut matrix = IDENTITY;
+
+    for item in value.split_inclusive(')') {
+        let Some((name, args)) = item.split_once('(') else {
+            continue;
+        };
+        let name = name.trim_matches(|c: char| c == ',' || c.is_whitespace());
+        let args = numbers(args);
+
+        let next = match (name, args.as_slice()) {
+            ("matrix", &[a, b, c, d, e, f]) => [a, b, c, d, e, f],
+            ("translate", &[x]) => [1.0, 0.0, 0.0, 1.0, x, 0.0],
+            ("translate", &[x, y]) => [1.0, 0.0, 0.0, 1.0, x, y],
+            ("scale", &[s]) => [s, 0.0, 0.0, s, 0.0, 0.0],
+            ("scale", &[x, y]) => [x, 0.0, 0.0, y, 0.0, 0.0],
+            ("rotate", &[angle]) | ("rotate", &[angle, _, _]) => {
+                let (sin, cos) = angle.to_radians().sin_cos();
+                let rotate = [cos, sin, -sin, cos, 0.0, 0.0];
+                if let [_, x, y] = args[..] {
+                    let to = [1.0, 0.0, 0.0, 1.0, x, y];
+                    let from = [1.0, 0.0, 0.0, 1.0, -x, -y];
+                    multiply(to, multiply(rotate, from))
+                } else {
+                    rotate
+                }
+            }
+            ("skewX", &[angle]) => [1.0, 0.0, angle.to_radians().tan(), 1.0, 0.0, 0.0],
+            ("skewY", &[angle]) => [1.0, angle.to_radians().tan(), 0.0, 1.0, 0.0, 0.0],
+            _ => panic!("{} has an unsupported transform {item:?}", path.display()),
+        };
+
+        matrix = multiply(matrix, next);
+    }
+
+    matrix
+}
+
+/// The points along each of a path's subpaths, in the path's own user units
+fn path_outlines(data: &str) -> Vec<Vec<[f64; 2]>> {
+    let mut outlines = vec![];
+    let mut points = vec![];
+    let mut current = [0.0; 2];
+    let mut start = [0.0; 2];
+    // the reflected control point for smooth curves
+    let mut control = None;
+
+    let mut commands = data
+        .match_indices(|c: char| c.is_ascii_alphabetic() && c != 'e' && c != 'E')
+        .peekable();
+
+    while let Some((idx, command)) = commands.next() {
+        let end = commands.peek().map_or(data.len(), |(next, _)| *next);
+        let args = numbers(&data[idx + 1..end]);
+
+        let command = command.chars().next().unwrap();
+        let relative = command.is_ascii_lowercase();
+        let offset = |current: [f64; 2], [x, y]: [f64; 2]| {
+            if relative {
+                [current[0] + x, current[1] + y]
+            } else {
+                [x, y]
+            }
+        };
+
+        let arity = match command.to_ascii_uppercase() {
+            'Z' => 0,
+            'H' | 'V' => 1,
+            'M' | 'L' | 'T' => 2,
+            'S' | 'Q' => 4,
+            'C' => 6,
+            'A' => 7,
+            _ => continue,
+        };
+
+        if arity == 0 {
+            current = start;
+            control = None;
+            continue;
+        }
+
+        for (i, args) in args.chunks_exact(arity).enumerate() {
+            let mut next_control = None;
+
+            match command.to_ascii_uppercase() {
+                'M' => {
+                    current = offset(current, [args[0], args[1]]);
+                    if i == 0 {
+                        start = current;
+                        if !points.is_empty() {
+                            outlines.push(std::mem::take(&mut points));
+                        }
+                    }
+                    points.push(current);
+                }
+                'L' | 'T' => {
+                    current = offset(current, [args[0], args[1]]);
+                    points.push(current);
+                }
+                'H' => {
+                    current[0] = if relative {
+                        current[0] + args[0]
+                    } else {
+                        args[0]
+                    };
+                    points.push(current);
+                }
+                'V' => {
+                    current[1] = if relative {
+                        current[1] + args[0]
+                    } else {
+                        args[0]
+                    };
+                    points.push(current);
+                }
+                'A' => {
+                    let to = offset(current, [args[5], args[6]]);
+                    let flags = [args[3] != 0.0, args[4] != 0.0];
+                    points.extend(arc_points(current, [args[0], args[1]], args[2], flags, to));
+                    current = to;
+                }
+                'C' | 'S' | 'Q' => {
+                    let upper = command.to_ascii_uppercase();
+                    let (c1, c2, to) = match upper {
+                        'C' => (
+                            offset(current, [args[0], args[1]]),
+                            offset(current, [args[2], args[3]]),
+                            offset(current, [args[4], args[5]]),
+                        ),
+                        'S' => (
+                            control.unwrap_or(current),
+                            offset(current, [args[0], args[1]]),
+                            offset(current, [args[2], args[3]]),
+                        ),
+                        _ => {
+                            let q = offset(current, [args[0], args[1]]);
+                            let to = offset(current, [args[2], args[3]]);
+                            // raise the quadratic to a cubic
+                            let lerp = |a: [f64; 2], b: [f64; 2]| {
+                                [0, 1].map(|i| a[i] + (b[i] - a[i]) * 2.0 / 3.0)
+                            };
+                            (lerp(current, q), lerp(to, q), to)
+                        }
+                    };
+
+                    for step in 1..=CURVE_STEPS {
+                        let t = step as f64 / CURVE_STEPS as f64;
+                        let u = 1.0 - t;
+                        points.push([0, 1].map(|i| {
+                            u * u * u * current[i]
+                                + 3.0 * u * u * t * c1[i]
+                                + 3.0 * u * t * t * c2[i]
+                                + t * t * t * to[i]
+                        }));
+                    }
+
+                    if upper != 'Q' {
+                        next_control = Some([2.0 * to[0] - c2[0], 2.0 * to[1] - c2[1]]);
+                    }
+                    current = to;
+                }
+                _ => unreachable!(),
+            }
+
+            control = next_control;
+        }
+    }
+
+    if !points.is_empty() {
+        outlines.push(points);
+    }
+    outlines
+}
+
+/// The points along an elliptical arc from `from` to `to`, including its extremes so the bounds
+/// are exact, following the endpoint to center conversion in the SVG spec
+fn arc_points(
+    from: [f64; 2],
+    [rx, ry]: [f64; 2],
+    rotation: f64,
+    [large, sweep]: [bool; 2],
+    to: [f64; 2],
+) -> Vec<[f64; 2]> {
+    use std::f64::consts::{PI, TAU};
+
+    let [mut rx, mut ry] = [rx.abs(), ry.abs()];
+    if from == to {
+        return vec![];
+    }
+    if rx == 0.0 || ry == 0.0 {
+        return vec![to];
+    }
+
+    let (sin, cos) = rotation.to_radians().sin_cos();
+    let [dx, dy] = [(from[0] - to[0]) * 0.5, (from[1] - to[1]) * 0.5];
+    let [x1, y1] = [cos * dx + sin * dy, -sin * dx + cos * dy];
+
+    // radii too small to reach between the endpoints are scaled up until they just do
+    let lambda = (x1 / rx).powi(2) + (y1 / ry).powi(2);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+
+    let num = (rx * ry).powi(2) - (rx * y1).powi(2) - (ry * x1).powi(2);
+    let den = (rx * y1).powi(2) + (ry * x1).powi(2);
+    let sign = if large != sweep { 1.0 } else { -1.0 };
+    let coef = sign * (num / den).max(0.0).sqrt();
+    let [cx1, cy1] = [coef * rx * y1 / ry, -coef * ry * x1 / rx];
+    let center = [
+        cos * cx1 - sin * cy1 + (from[0] + to[0]) * 0.5,
+        sin * cx1 + cos * cy1 + (from[1] + to[1]) * 0.5,
+    ];
+
+    let start = ((y1 - cy1) / ry).atan2((x1 - cx1) / rx);
+    let end = ((-y1 - cy1) / ry).atan2((-x1 - cx1) / rx);
+    let mut delta = (end - start).rem_euclid(TAU);
+    if !sweep && delta > 0.0 {
+        delta -= TAU;
+    }
+
+    let point = |angle: f64| {
+        let (s, c) = angle.sin_cos();
+        [
+            center[0] + rx * cos * c - ry * sin * s,
+            center[1] + rx * sin * c + ry * cos * s,
+        ]
+    };
+
+    let steps = ((delta.abs() / TAU * TURN_STEPS as f64).ceil() as usize).max(1);
+    let mut ts = (1..=steps)
+        .map(|step| step as f64 / steps as f64)
+        .collect::<Vec<_>>();
+
+    // the angles where the rotated ellipse is widest and tallest
+    let extremes = [(-ry * sin).atan2(rx * cos), (ry * cos).atan2(rx * sin)];
+    for angle in extremes.into_iter().flat_map(|a| [a, a + PI]) {
+        let turned = if sweep { angle - start } else { start - angle };
+        let t = turned.rem_euclid(TAU) / delta.abs();
+        if t < 1.0 {
+            ts.push(t);
+        }
+    }
+    ts.sort_by(f64::total_cmp);
+
+    ts.into_iter().map(|t| point(start + delta * t)).collect()
+}
+
+/// The points along a shape element's outlines, or `None` if it doesn't draw anything
+fn shape_outlines(tag: &Tag) -> Option<Vec<Vec<[f64; 2]>>> {
+    let num = |name: &str| {
+        tag.attr(name)
+            .and_then(|value| numbers(value).first().copied())
+            .unwrap_or(0.0)
+    };
+
+    let points = match tag.name {
+        "path" => return Some(path_outlines(tag.attr("d")?)),
+        "polygon" | "polyline" => numbers(tag.attr("points")?)
+            .chunks_exact(2)
+            .map(|p| [p[0], p[1]])
+            .collect(),
+        "line" => vec![[num("x1"), num("y1")], [num("x2"), num("y2")]],
+        "rect" => {
+            let [x, y, w, h] = [num("x"), num("y"), num("width"), num("height")];
+            vec![[x, y], [x + w, y], [x + w, y + h], [x, y + h]]
+        }
+        "circle" | "ellipse" => {
+            let [cx, cy] = [num("cx"), num("cy")];
+            let [rx, ry] = if tag.name == "circle" {
+                [num("r"); 2]
+            } else {
+                [num("rx"), num("ry")]
+            };
+            (0..TURN_STEPS)
+                .map(|i| {
+                    let (sin, cos) =
+                        (i as f64 * std::f64::consts::TAU / TURN_STEPS as f64).sin_cos();
+                    [cx + rx * cos, cy + ry * sin]
+                })
+                .collect()
+        }
+        _ => return None,
+    };
+
+    Some(vec![points])
+}
+
+/// The outlines of everything the document draws, in its user units
+fn artwork_outlines(tags: &[Tag], path: &Path) -> Vec<Vec<[f64; 2]>> {
+    // elements that hold definitions or metadata rather than drawn artwork
+    const HIDDEN: &[&str] = &[
+        "defs",
+        "metadata",
+        "clipPath",
+        "mask",
+        "marker",
+        "pattern",
+        "symbol",
+        "title",
+        "desc",
+        "sodipodi:namedview",
+    ];
+
+    let mut stack = vec![(IDENTITY, false)];
+    let mut outlines = vec![];
+
+    for tag in tags {
+        let (parent, hidden) = *stack.last().unwrap();
+
+        if tag.kind == TagKind::Close {
+            if stack.len() > 1 {
+                stack.pop();
+            }
+            continue;
+        }
+
+        let matrix = tag
+            .attr("transform")
+            .map_or(parent, |value| multiply(parent, transform(value, path)));
+        let hidden = hidden || HIDDEN.contains(&tag.name);
+
+        if !hidden {
+            for outline in shape_outlines(tag).into_iter().flatten() {
+                outlines.push(
+                    outline
+                        .into_iter()
+                        .map(|point| apply(matrix, point))
+                        .collect(),
+                );
+            }
+        }
+
+        if tag.kind == TagKind::Open {
+            stack.push((matrix, hidden));
+        }
+    }
+
+    outlines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path_points(data: &str) -> Vec<[f64; 2]> {
+        path_outlines(data).concat()
+    }
+
+    fn bounds(points: &[[f64; 2]]) -> [[f64; 2]; 2] {
+        let mut bounds = [[f64::MAX; 2], [f64::MIN; 2]];
+        for [x, y] in points {
+            bounds = [
+                [bounds[0][0].min(*x), bounds[0][1].min(*y)],
+                [bounds[1][0].max(*x), bounds[1][1].max(*y)],
+            ];
+        }
+        bounds
+    }
+
+    fn assert_near(actual: [[f64; 2]; 2], expected: [[f64; 2]; 2]) {
+        let near = (0..2).all(|i| (0..2).all(|j| (actual[i][j] - expected[i][j]).abs() < 1e-6));
+        assert!(near, "expected {expected:?}, got {actual:?}");
+    }
+
+    fn document(name: &str, contents: &str) -> Svg {
+        let path = std::env::temp_dir().join(format!("common-svg-{name}.svg"));
+        fs::write(&path, contents).unwrap();
+        svg(path)
+    }
+
+    #[test]
+    fn horizontal_and_vertical_lines() {
+        let points = path_points("M 1 2 H 5 V 7 h -2 v -3");
+        assert_eq!(
+            points,
+            [[1.0, 2.0], [5.0, 2.0], [5.0, 7.0], [3.0, 7.0], [3.0, 4.0]]
+        );
+    }
+
+    #[test]
+    fn cubic_curves_bulge_past_their_endpoints() {
+        let points = path_points("M 0 0 C 0 10 10 10 10 0");
+        assert_near(bounds(&points), [[0.0, 0.0], [10.0, 7.5]]);
+    }
+
+    #[test]
+    fn smooth_curves_reflect_the_previous_control_point() {
+        let points = path_points("M 0 0 C 0 10 10 10 10 0 s 10 -10 10 0");
+        assert_near(bounds(&points), [[0.0, -7.5], [20.0, 7.5]]);
+        assert_eq!(points.last(), Some(&[20.0, 0.0]));
+    }
+
+    #[test]
+    fn quadratic_curves() {
+        let points = path_points("M 0 0 Q 5 10 10 0");
+        assert_near(bounds(&points), [[0.0, 0.0], [10.0, 5.0]]);
+    }
+
+    #[test]
+    fn arcs_follow_their_sweep() {
+        assert_near(
+            bounds(&path_points("M 0 0 A 5 5 0 0 1 10 0")),
+            [[0.0, -5.0], [10.0, 0.0]],
+        );
+        assert_near(
+            bounds(&path_points("M 0 0 A 5 5 0 0 0 10 0")),
+            [[0.0, 0.0], [10.0, 5.0]],
+        );
+    }
+
+    #[test]
+    fn large_arcs_reach_round_the_far_side() {
+        let points = path_points("M 4.8 4 H 25.2 A 15 15 0 1 1 4.8 4 Z");
+        let center = 4.0 + (15.0_f64.powi(2) - 10.2_f64.powi(2)).sqrt();
+        assert_near(bounds(&points), [[0.0, 4.0], [30.0, center + 15.0]]);
+    }
+
+    #[test]
+    fn rotated_arcs_reach_their_extremes() {
+        // half of an ellipse turned on end, from its bottom tip to its top tip
+        let points = path_points("M 0 -10 A 10 5 90 0 1 0 10");
+        assert_near(bounds(&points), [[0.0, -10.0], [5.0, 10.0]]);
+    }
+
+    #[test]
+    fn view_box_with_size() {
+        let doc = document(
+            "view-box-with-size",
+            r#"<svg width="30mm" height="20mm" viewBox="0 0 60 40"><rect x="6" y="4" width="12" height="8"/></svg>"#,
+        );
+        assert_eq!(doc.size(), [30.0, 20.0]);
+        assert_near(doc.bounds(), [[3.0, 14.0], [9.0, 18.0]]);
+    }
+
+    #[test]
+    fn view_box_with_width_keeps_its_aspect() {
+        let doc = document(
+            "view-box-with-width",
+            r#"<svg width="50mm" viewBox="0 0 100 20"/>"#,
+        );
+        assert_eq!(doc.size(), [50.0, 10.0]);
+    }
+
+    #[test]
+    fn view_box_alone_is_in_px() {
+        let doc = document("view-box-alone", r#"<svg viewBox="0 0 96 48"/>"#);
+        assert_near([[0.0; 2], doc.size()], [[0.0; 2], [25.4, 12.7]]);
+    }
+
+    #[test]
+    fn size_alone_measures_user_units_in_px() {
+        let doc = document(
+            "size-alone",
+            r#"<svg width="96" height="48"><rect width="48" height="48"/></svg>"#,
+        );
+        let [x, y, w, h] = doc.view_box();
+        assert_near([[x, y], [w, h]], [[0.0, 0.0], [96.0, 48.0]]);
+        assert_near(doc.bounds(), [[0.0, 0.0], [12.7, 12.7]]);
+    }
+
+    #[test]
+    fn each_subpath_is_its_own_outline() {
+        let outlines = path_outlines("M 0 0 H 10 V 10 Z M 2 2 h 6 v 6 z");
+        assert_eq!(
+            outlines,
+            [
+                vec![[0.0, 0.0], [10.0, 0.0], [10.0, 10.0]],
+                vec![[2.0, 2.0], [8.0, 2.0], [8.0, 8.0]],
+            ]
+        );
+    }
+
+    #[test]
+    fn outlines_are_in_millimeters_from_the_bottom_left() {
+        let doc = document(
+            "outlines",
+            r#"<svg width="20mm" height="10mm" viewBox="0 0 40 20"><rect x="4" y="2" width="8" height="6"/></svg>"#,
+        );
+        assert_eq!(
+            doc.outlines(),
+            [vec![[2.0, 9.0], [6.0, 9.0], [6.0, 6.0], [2.0, 6.0]]]
+        );
+    }
+}
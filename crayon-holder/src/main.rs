@@ -1,89 +1,523 @@
 use rsolid::*;
 
-const BASE_H: f64 = 116.0;
-const BASE_W: f64 = 93.25;
 const BASE_D: f64 = 20.0 * 1.5;
 const BASE_ROUNDING: f64 = 11.0;
-const S_SLOT_W: f64 = 14.0;
-const S_SLOT_H: f64 = 26.5;
-const S_SLOT_SPACING: f64 = 4.5;
-const R_SLOT_D: f64 = 13.15;
-const R_SLOT_R: f64 = R_SLOT_D * 0.5;
+const BOTTOM_D: f64 = 4.0;
+/// How deep slots go unless an item asks for something else
+const SLOT_D: f64 = BASE_D - BOTTOM_D;
+/// The least material between two slots, and between a slot and the outside
+const WALL: f64 = 4.5;
+/// How far the layout search steps the row width when packing
+const PACK_STEP: f64 = 1.0;
+/// How far cuts reach past the faces they open
+const OVERLAP: f64 = 0.5;
+const DRAIN_D: f64 = 3.0;
+/// The rim left around the underside relief, which the holder stands on
+const RELIEF_RIM: f64 = 6.0;
+const RELIEF_D: f64 = 1.0;
 
-fn base_shape() -> Object<2> {
-    square([BASE_W - BASE_ROUNDING, BASE_H - BASE_ROUNDING])
-        .center(true)
-        .minkowski(circle(BASE_ROUNDING))
+/// The cross-section of the hole an item stands in
+#[derive(Clone, Copy, Debug)]
+enum Section {
+    /// A rectangle with filleted corners of radius `r`
+    Rect {
+        w: f64,
+        h: f64,
+        r: f64,
+    },
+    Round {
+        d: f64,
+    },
 }
 
-fn s_slot() -> Object<2> {
-    let w = S_SLOT_W;
-    let h = S_SLOT_H;
-    let f = &mask::fillet(2.0);
-    let mut mask = f >> fwd(h / 2.0) >> right(w / 2.0);
-    mask += f >> rotate_z(90) >> fwd(h / 2.0) >> left(w / 2.0);
-    mask += f >> rotate_z(180) >> back(h / 2.0) >> left(w / 2.0);
-    mask += f >> rotate_z(-90) >> back(h / 2.0) >> right(w / 2.0);
+impl Section {
+    fn size(&self) -> [f64; 2] {
+        match *self {
+            Self::Rect { w, h, .. } => [w, h],
+            Self::Round { d } => [d, d],
+        }
+    }
+
+    fn shape(&self) -> Object<2> {
+        match *self {
+            Self::Rect { w, h, r } => {
+                let f = &mask::fillet(r);
+                let mut mask = f >> fwd(h / 2.0) >> right(w / 2.0);
+                mask += f >> rotate_z(90) >> fwd(h / 2.0) >> left(w / 2.0);
+                mask += f >> rotate_z(180) >> back(h / 2.0) >> left(w / 2.0);
+                mask += f >> rotate_z(-90) >> back(h / 2.0) >> right(w / 2.0);
+
+                square([w, h]).center(true) - mask
+            }
+            Self::Round { d } => circle(d * 0.5).into(),
+        }
+    }
+
+    /// Rounds over the rim of the hole, for a top face at z = 0
+    fn entry(&self, r: f64) -> Object {
+        // the material side of the rim is towards +x and -y
+        let profile = mask::fillet(r) >> rotate_z(180);
 
-    square([w, h]).center(true) - mask
+        match *self {
+            Self::Rect { w, h, .. } => {
+                let edge = |length: f64, offset: f64| {
+                    &profile >> linear_extrude(length).center(true) >> rotate_x(90) >> right(offset)
+                };
+                let side = edge(h, w * 0.5);
+                let end = edge(w, h * 0.5) >> rotate_z(90);
+
+                &side + (&side >> rotate_z(180)) + &end + (&end >> rotate_z(180))
+            }
+            Self::Round { d } => profile >> right(d * 0.5) >> rotate_extrude(),
+        }
+    }
 }
 
-fn s_slots() -> Object<2> {
-    let spacing = S_SLOT_W * 1.5 + S_SLOT_SPACING;
+/// Something to hold, how many of it and how its slots are cut
+#[derive(Clone, Copy, Debug)]
+struct Item {
+    name: &'static str,
+    section: Section,
+    count: usize,
+    depth: f64,
+    /// Degrees the slot leans back from upright, so tall items rest against the back
+    lean: f64,
+}
+
+impl Item {
+    const fn new(name: &'static str, section: Section) -> Self {
+        Self {
+            name,
+            section,
+            count: 1,
+            depth: SLOT_D,
+            lean: 0.0,
+        }
+    }
+
+    const fn count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    const fn depth(mut self, depth: f64) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    const fn lean(mut self, degrees: f64) -> Self {
+        self.lean = degrees;
+        self
+    }
+
+    /// How far back the top of the slot sits from its bottom
+    fn run(&self) -> f64 {
+        self.depth * self.lean.to_radians().tan()
+    }
+
+    /// The area the slot takes up on the top face, from its top opening back to its bottom
+    fn footprint(&self) -> [f64; 2] {
+        let [w, h] = self.section.size();
+        [w, h / self.lean.to_radians().cos() + self.run()]
+    }
+
+    /// The hole for one of these, centered on its footprint with its top face at `top`
+    fn hole(&self, top: f64, entry: f64) -> Object {
+        let cos = self.lean.to_radians().cos();
+        let [w, h] = self.footprint();
+        let height = self.depth + OVERLAP;
 
-    let shape = s_slot() + (s_slot() >> back(S_SLOT_H * 1.20 + S_SLOT_SPACING));
+        // a long prism tilted about the middle of the slot, trimmed flat at the bottom and top
+        let prism = self.section.shape()
+            >> linear_extrude((height + h) * 2.0 / cos).center(true)
+            >> rotate_x(-self.lean)
+            >> up(self.depth * 0.5);
+        let slab = cube([w + 2.0, h + 2.0, height]).center(true) >> up(height * 0.5);
 
-    let first = shape >> left(spacing * 1.5) >> back(S_SLOT_H * 0.25);
+        let mut hole = prism & slab;
 
-    let mut s = first.clone();
+        if entry > 0.0 {
+            hole += self.section.entry(entry)
+                >> scale([1.0, 1.0 / cos, 1.0])
+                >> fwd(self.run() * 0.5)
+                >> up(self.depth);
+        }
 
-    for i in 1..4 {
-        s += &first >> right(spacing * i as f64);
+        hole >> up(top - self.depth)
     }
+}
+
+const CRAYON: Item = Item::new("crayon", Section::Round { d: 9.5 });
+const MARKER: Item = Item::new("marker", Section::Round { d: 13.15 });
+const GLUE_STICK: Item = Item::new("glue stick", Section::Round { d: 23.0 });
+const SCISSORS: Item = Item::new(
+    "scissors",
+    Section::Rect {
+        w: 14.0,
+        h: 26.5,
+        r: 2.0,
+    },
+);
+
+#[derive(Clone, Copy, Debug)]
+enum Layout {
+    /// Every slot gets a cell the size of the biggest one, in the squarest grid that holds them
+    Grid,
+    /// Rows of slots, tallest first, with the row width that gives the smallest base
+    Packed,
+}
 
-    s
+/// A step that raises the back rows of slots, widened to every row holding a slot too deep for
+/// the lower floor
+#[derive(Clone, Copy, Debug)]
+struct Tier {
+    rows: usize,
+    rise: f64,
 }
 
-fn r_slot() -> Object<2> {
-    circle(R_SLOT_R).into()
+/// A slot's item, its row counting from the back and its footprint's center, measured from the
+/// middle of the base
+#[derive(Clone, Copy, Debug)]
+struct Slot {
+    item: Item,
+    row: usize,
+    at: [f64; 2],
+}
+
+#[derive(Clone, Debug)]
+struct Holder {
+    name: &'static str,
+    items: Vec<Item>,
+    layout: Layout,
+    tier: Option<Tier>,
+    /// The radius the slot rims are rounded over by, or 0 for sharp rims
+    entry: f64,
+    /// Holes through the floor under every slot, so dust falls out
+    drains: bool,
+    /// A recess in the underside, so the holder stands on its rim and dust can get out
+    relief: bool,
+}
+
+impl Default for Holder {
+    fn default() -> Self {
+        Self {
+            name: "crayon_holder",
+            items: vec![],
+            layout: Layout::Packed,
+            tier: None,
+            entry: 0.0,
+            drains: false,
+            relief: false,
+        }
+    }
 }
 
-fn r_slots() -> Object<2> {
-    let spacing = 8.5;
-    let first = r_slot() >> fwd(BASE_H * 0.5 - spacing) >> left(BASE_W * 0.5 - spacing);
+impl Holder {
+    /// How far slots stay from the straight edges so the corner rounding doesn't eat into them
+    fn margin() -> f64 {
+        let sqrt2 = std::f64::consts::SQRT_2;
+        WALL.max((BASE_ROUNDING * (sqrt2 - 1.0) + WALL) / sqrt2)
+    }
+
+    fn slot_items(&self) -> Vec<Item> {
+        self.items
+            .iter()
+            .flat_map(|item| std::iter::repeat_n(*item, item.count))
+            .collect()
+    }
+
+    /// Where every slot goes and the size of the base that holds them
+    fn layout(&self) -> (Vec<Slot>, [f64; 2]) {
+        let items = self.slot_items();
+        assert!(
+            !items.is_empty(),
+            "the {} holder has nothing to hold",
+            self.name
+        );
+
+        let footprints = items.iter().map(Item::footprint).collect::<Vec<_>>();
+        let (places, content) = match self.layout {
+            Layout::Grid => grid(&footprints),
+            Layout::Packed => packed(&footprints),
+        };
 
-    let spacing = spacing + R_SLOT_D + R_SLOT_R * 0.5;
-    let first = first.clone() + (first >> back(spacing));
+        let slots = items
+            .into_iter()
+            .zip(places)
+            .map(|(item, (row, [x, y]))| Slot {
+                item,
+                row,
+                // rows are laid out from the back
+                at: [x - content[0] * 0.5, content[1] * 0.5 - y],
+            })
+            .collect();
 
-    let mut shape = first.clone();
+        let margin = Self::margin() * 2.0;
+        (slots, [content[0] + margin, content[1] + margin])
+    }
 
-    for i in 1..4 {
-        shape += &first >> right(spacing * i as f64);
+    /// The floor left under the slots, above the underside relief
+    fn floor(&self) -> f64 {
+        if self.relief {
+            BOTTOM_D + RELIEF_D
+        } else {
+            BOTTOM_D
+        }
     }
 
-    shape
+    /// How many rows from the back the tier raises: the ones it asks for, and every row with a
+    /// slot that would cut through the floor without it
+    fn tier_rows(&self, slots: &[Slot]) -> usize {
+        let Some(tier) = self.tier else {
+            return 0;
+        };
+
+        slots
+            .iter()
+            .filter(|slot| BASE_D - slot.item.depth < self.floor())
+            .map(|slot| slot.row + 1)
+            .fold(tier.rows, usize::max)
+    }
+
+    /// Where the tier's front face sits, halfway between the rows either side of it
+    fn tier_front(rows: usize, slots: &[Slot], size: [f64; 2]) -> f64 {
+        let back_rows = slots
+            .iter()
+            .filter(|slot| slot.row < rows)
+            .map(|slot| slot.at[1] - slot.item.footprint()[1] * 0.5)
+            .fold(f64::INFINITY, f64::min);
+        let front_rows = slots
+            .iter()
+            .filter(|slot| slot.row >= rows)
+            .map(|slot| slot.at[1] + slot.item.footprint()[1] * 0.5)
+            .fold(-size[1] * 0.5, f64::max);
+
+        assert!(
+            back_rows.is_finite(),
+            "a tier needs at least one row of slots"
+        );
+
+        (back_rows + front_rows) * 0.5
+    }
+
+    /// The height of the top face a slot opens onto, with the tier raising `rows` rows
+    fn top(&self, slot: &Slot, rows: usize) -> f64 {
+        match self.tier {
+            Some(tier) if slot.row < rows => BASE_D + tier.rise,
+            _ => BASE_D,
+        }
+    }
+
+    /// Checks every slot leaves the floor standing under it
+    fn check_floors(&self, slots: &[Slot]) {
+        let floor = self.floor();
+        let rows = self.tier_rows(slots);
+
+        for slot in slots {
+            let bottom = self.top(slot, rows) - slot.item.depth;
+            assert!(
+                bottom >= floor,
+                "{} slots {}mm deep leave a {bottom:.1}mm floor in the {} holder, which needs {floor}mm",
+                slot.item.name,
+                slot.item.depth,
+                self.name
+            );
+        }
+    }
+
+    fn base_shape(size: [f64; 2]) -> Object<2> {
+        square(size.map(|s| s - BASE_ROUNDING * 2.0))
+            .center(true)
+            .minkowski(circle(BASE_ROUNDING))
+    }
+
+    fn model(&self) -> Object {
+        let (slots, size) = self.layout();
+        self.check_floors(&slots);
+
+        let shape = Self::base_shape(size);
+        let rows = self.tier_rows(&slots);
+        let mut body = &shape >> linear_extrude(BASE_D);
+
+        if let Some(tier) = self.tier {
+            let front = Self::tier_front(rows, &slots, size);
+            let back_part = square([size[0], size[1] * 0.5 - front]).into_object()
+                >> translate([-size[0] * 0.5, front]);
+            body += (&shape & back_part) >> linear_extrude(BASE_D + tier.rise);
+        }
+
+        for slot in &slots {
+            let top = self.top(slot, rows);
+            let bottom = top - slot.item.depth;
+            let [x, y] = slot.at;
+
+            body -= slot.item.hole(top, self.entry) >> translate([x, y, 0.0]);
+
+            if self.drains {
+                body -= cylinder(bottom + OVERLAP * 2.0, DRAIN_D * 0.5)
+                    >> translate([x, y - slot.item.run() * 0.5, -OVERLAP]);
+            }
+        }
+
+        if self.relief {
+            let inset = size.map(|s| s - RELIEF_RIM * 2.0);
+            body -= Self::base_shape(inset) >> linear_extrude(RELIEF_D + OVERLAP) >> down(OVERLAP);
+        }
+
+        body
+    }
+}
+
+/// The area of the base around content of `size`
+fn base_area(size: [f64; 2]) -> f64 {
+    let margin = Holder::margin() * 2.0;
+    (size[0] + margin) * (size[1] + margin)
 }
 
-fn base() -> Object {
-    let shape = base_shape() - s_slots() - r_slots();
+/// Each footprint's row and center, measured from the back left corner of the content, and the
+/// content size
+fn grid(footprints: &[[f64; 2]]) -> (Vec<(usize, [f64; 2])>, [f64; 2]) {
+    let cell = footprints.iter().fold([0.0f64; 2], |cell, [w, h]| {
+        [cell[0].max(*w), cell[1].max(*h)]
+    });
+    let span = |cells: usize, size: f64| cells as f64 * (size + WALL) - WALL;
 
-    shape >> linear_extrude(BASE_D)
+    let columns = (1..=footprints.len())
+        .min_by(|a, b| {
+            let size = |columns: usize| {
+                let rows = footprints.len().div_ceil(columns);
+                [span(columns, cell[0]), span(rows, cell[1])]
+            };
+            base_area(size(*a)).total_cmp(&base_area(size(*b)))
+        })
+        .unwrap();
+    let rows = footprints.len().div_ceil(columns);
+
+    let places = (0..footprints.len())
+        .map(|i| {
+            let [column, row] = [i % columns, i / columns];
+            let at = [
+                column as f64 * (cell[0] + WALL) + cell[0] * 0.5,
+                row as f64 * (cell[1] + WALL) + cell[1] * 0.5,
+            ];
+            (row, at)
+        })
+        .collect();
+
+    (places, [span(columns, cell[0]), span(rows, cell[1])])
 }
 
-fn bottom() -> Object {
-    let shape = base_shape();
+/// Fills rows no wider than `width` with the footprints, tallest first
+fn shelves(footprints: &[[f64; 2]], width: f64) -> (Vec<(usize, [f64; 2])>, [f64; 2]) {
+    let mut order = (0..footprints.len()).collect::<Vec<_>>();
+    order.sort_by(|a, b| footprints[*b][1].total_cmp(&footprints[*a][1]));
+
+    let mut places = vec![(0, [0.0; 2]); footprints.len()];
+    let mut row = 0;
+    let mut x = 0.0;
+    let mut y = 0.0;
+    let mut row_h = 0.0;
+    let mut content = [0.0f64; 2];
+
+    for i in order {
+        let [w, h] = footprints[i];
+
+        if x > 0.0 && x + w > width {
+            row += 1;
+            y += row_h + WALL;
+            x = 0.0;
+            row_h = 0.0;
+        }
 
-    shape >> linear_extrude(4.0)
+        // the first slot in a row is the tallest, so it sets the row height
+        if x == 0.0 {
+            row_h = h;
+        }
+
+        places[i] = (row, [x + w * 0.5, y + row_h * 0.5]);
+        content = [content[0].max(x + w), y + row_h];
+        x += w + WALL;
+    }
+
+    (places, content)
+}
+
+/// Each footprint's row and center, measured from the back left corner of the content, and the
+/// content size
+fn packed(footprints: &[[f64; 2]]) -> (Vec<(usize, [f64; 2])>, [f64; 2]) {
+    let widest = footprints.iter().map(|[w, _]| *w).fold(0.0, f64::max);
+    let total = footprints.iter().map(|[w, _]| w + WALL).sum::<f64>();
+
+    let steps = ((total - widest) / PACK_STEP).ceil() as usize;
+    (0..=steps)
+        .map(|step| shelves(footprints, widest + step as f64 * PACK_STEP))
+        .min_by(|(_, a), (_, b)| base_area(*a).total_cmp(&base_area(*b)))
+        .unwrap()
+}
+
+fn holders() -> Vec<Holder> {
+    vec![
+        Holder {
+            items: vec![SCISSORS.count(8), MARKER.count(8)],
+            ..Default::default()
+        },
+        Holder {
+            name: "art_box",
+            items: vec![
+                CRAYON.count(24).depth(20.0),
+                MARKER.count(12).lean(15.0),
+                GLUE_STICK.count(2),
+                SCISSORS.count(2),
+            ],
+            ..Default::default()
+        },
+        Holder {
+            name: "art_box_tiered",
+            items: vec![
+                MARKER.count(8).lean(15.0).depth(SLOT_D + 10.0),
+                GLUE_STICK.count(2).depth(SLOT_D - RELIEF_D),
+                CRAYON.count(16).depth(20.0),
+            ],
+            tier: Some(Tier {
+                rows: 1,
+                rise: 15.0,
+            }),
+            entry: 1.5,
+            drains: true,
+            relief: true,
+            ..Default::default()
+        },
+        Holder {
+            name: "marker_grid",
+            items: vec![MARKER.count(16)],
+            layout: Layout::Grid,
+            ..Default::default()
+        },
+    ]
 }
 
 fn main() {
-    let main = base() + bottom();
+    let holders = holders();
 
     let settings = fragment_count(150).preview(25);
 
-    let main = settings.apply(&main);
-
     let targets = &["amf"];
 
-    rsolid::export!(main, targets);
+    for holder in &holders {
+        let main = settings.apply(&holder.model());
+        rsolid::export!(main, holder.name, targets);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_holder_keeps_its_floor() {
+        for holder in holders() {
+            let (slots, _) = holder.layout();
+            holder.check_floors(&slots);
+        }
+    }
 }
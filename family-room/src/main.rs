@@ -7,8 +7,8 @@ fn main() {
 
     macro_rules! shape {
         ($name:literal, $w:expr, $h:expr) => {{
-            let shape = square([$w / SCALE, $h / SCALE]) >> linear_extrude(2.0);
-            out.push(($name, shape));
+            let profile = square([$w / SCALE, $h / SCALE]).into_object();
+            out.push(($name, profile));
         }};
     }
 
@@ -26,8 +26,11 @@ fn main() {
 
     let settings = fragment_count(50).preview(25);
 
-    out.iter().for_each(|(name, shape)| {
-        let v = settings.apply(shape);
+    out.iter().for_each(|(name, profile)| {
+        let v = settings.apply(&(profile >> linear_extrude(2.0)));
         rsolid::export!(v, format!("{name}"), targets);
+
+        let v = settings.apply(profile);
+        rsolid::export!(v, format!("{name}"), &["dxf"]);
     });
 }
@@ -1,3 +1,4 @@
+use common::svg::Svg;
 use rayon::prelude::*;
 use rsolid::*;
 
@@ -11,9 +12,45 @@ const TOLERANCE: f64 = 0.75;
 
 const MAGNET_INSET: f64 = 1.5;
 const MAGNET_PLACEMENT: f64 = (TILE_W - MAGNET_H - TOLERANCE) * 0.5 - MAGNET_INSET;
+/// The shortest roof trim with room for a magnet pocket
+const ROOF_TRIM_MIN: f64 = MAGNET_H + TOLERANCE + MAGNET_INSET * 2.0;
+
+// bevel pockets sit square to the 45° bevel, MAGNET_INSET behind it and centered in the
+// tile's thickness, where the bevel is (TILE_W - TILE_D) / 2 from the center
+const BEVEL_MAGNET_PLACEMENT: f64 = (TILE_W - TILE_D) * 0.5
+    - (MAGNET_INSET + (MAGNET_H + TOLERANCE) * 0.5) * std::f64::consts::SQRT_2;
+/// What's left of the front and back faces over the corners of a bevel pocket
+const BEVEL_MAGNET_SKIN: f64 = 0.4;
+
+const DECORATION_CLEARANCE: f64 = 1.0;
+const DECORATION_WALL: f64 = 1.5;
 
 const C_MAGNET_W: f64 = 6.0;
 const C_MAGNET_H: f64 = 2.0;
+const C_MAGNET_CAVITY_R: f64 = C_MAGNET_W * 0.5 + 0.85;
+
+const ACCESSORY_FLOOR: f64 = 0.6;
+const ACCESSORY_ROUNDING: f64 = 1.0;
+
+/// The gumdrop artwork is drawn at two and a half times the printed size
+const GUMDROP_SCALE: f64 = 0.4;
+
+const SNOWMAN_ROUNDING: f64 = 2.0;
+const SNOWMAN_HAT_H: f64 = 9.5;
+const SNOWMAN_PEG_R: f64 = 1.0;
+const SNOWMAN_PEG_DEPTH: f64 = 3.0;
+const SNOWMAN_PEG_CLEARANCE: f64 = 0.15;
+
+const TREE_ROUNDING: f64 = 3.0;
+const TREE_FROSTING_TOP: f64 = 4.1;
+const TREE_MAGNET_TOP: f64 = 2.0;
+const TREE_MAGNET_BOTTOM: f64 = -1.2;
+const TREE_MAGNET_MAX_GAP: f64 = 2.0;
+const TREE_HUB_WALL: f64 = 1.0;
+const TREE_TOPPER_BASE_H: f64 = 4.0;
+const TREE_TOPPER_R: f64 = 12.0;
+const TREE_TOPPER_ROUNDING: f64 = 0.5;
+const TREE_TOPPER_MAGNET: f64 = (C_MAGNET_H + TOLERANCE) * 0.5 + 0.4;
 
 fn magnet_cavity() -> Object {
     cube([
@@ -25,8 +62,23 @@ fn magnet_cavity() -> Object {
     .into()
 }
 
+/// A magnet pocket turned 45° about its length to sit square to a bevel. The section is square,
+/// so the same pocket fits the bevel on every side. Its clearance corners are trimmed to leave
+/// `BEVEL_MAGNET_SKIN` under the faces; the magnet itself stays clear of them.
+fn bevel_magnet_cavity() -> Object {
+    let magnet = (MAGNET_H + MAGNET_W) * std::f64::consts::FRAC_1_SQRT_2;
+    let room = TILE_D - BEVEL_MAGNET_SKIN * 2.0;
+    assert!(
+        magnet <= room,
+        "a turned {MAGNET_H}x{MAGNET_W}mm magnet is {magnet:.2}mm deep, but the tile has {room:.2}mm"
+    );
+
+    let faces = cube([TILE_W, TILE_W, room]).center(true);
+    (magnet_cavity() >> rotate_x(45)) & faces
+}
+
 fn c_magnet_cavity() -> Object {
-    cylinder(C_MAGNET_H + TOLERANCE, C_MAGNET_W * 0.5 + 0.85)
+    cylinder(C_MAGNET_H + TOLERANCE, C_MAGNET_CAVITY_R)
         .center(true)
         .into()
 }
@@ -62,6 +114,97 @@ fn tile_square_cavities(cavities: [bool; 4]) -> Object {
     base - out
 }
 
+/// Debosses the tolerance and version under a square tile, clear of its magnet cavities
+fn tile_label(tile: Object) -> Object {
+    let along = [MAGNET_L + TOLERANCE, MAGNET_H + TOLERANCE];
+    let across = [along[1], along[0]];
+
+    common::label(format!("T{TOLERANCE} {}", common::version!()))
+        .size(2.5)
+        .underside(-TILE_D * 0.5, common::Region::rect([TILE_W, TILE_W]))
+        .keep_out(common::Region::Rect {
+            center: [0.0, MAGNET_PLACEMENT],
+            size: along,
+        })
+        .keep_out(common::Region::Rect {
+            center: [0.0, -MAGNET_PLACEMENT],
+            size: along,
+        })
+        .keep_out(common::Region::Rect {
+            center: [MAGNET_PLACEMENT, 0.0],
+            size: across,
+        })
+        .keep_out(common::Region::Rect {
+            center: [-MAGNET_PLACEMENT, 0.0],
+            size: across,
+        })
+        .apply(tile)
+}
+
+fn tile_square_bevel(bevels: [bool; 4]) -> Object {
+    let tile = tile_square_cavities(bevels.map(|bevel| !bevel));
+
+    let diagonal = TILE_D * std::f64::consts::SQRT_2;
+    let cut =
+        cube([TILE_W * 2.0, diagonal, diagonal]).center(true) >> rotate_x(45) >> down(TILE_D * 0.5);
+    let side_cut = &cut >> rotate_z(90);
+
+    let mut cuts = empty().into_object();
+    let mut cavities = empty().into_object();
+
+    if bevels[0] {
+        cuts += &cut >> fwd(TILE_W * 0.5);
+        cavities += bevel_magnet_cavity() >> fwd(BEVEL_MAGNET_PLACEMENT);
+    }
+    if bevels[1] {
+        cuts += &side_cut >> right(TILE_W * 0.5);
+        cavities += bevel_magnet_cavity() >> rotate_z(90) >> right(BEVEL_MAGNET_PLACEMENT);
+    }
+    if bevels[2] {
+        cuts += &cut >> back(TILE_W * 0.5);
+        cavities += bevel_magnet_cavity() >> back(BEVEL_MAGNET_PLACEMENT);
+    }
+    if bevels[3] {
+        cuts += &side_cut >> left(TILE_W * 0.5);
+        cavities += bevel_magnet_cavity() >> rotate_z(90) >> left(BEVEL_MAGNET_PLACEMENT);
+    }
+
+    tile - cuts - cavities
+}
+
+fn corner_outside() -> Object {
+    corner(true)
+}
+
+fn corner_inside() -> Object {
+    corner(false)
+}
+
+fn corner(outside: bool) -> Object {
+    let rounding = 2.0;
+
+    let leg_x = square([TILE_D * 2.0, TILE_D]).center(true) >> back(TILE_D * 0.5);
+    let leg_y = square([TILE_D, TILE_D * 2.0]).center(true) >> left(TILE_D * 0.5);
+
+    let mut shape = leg_x + leg_y;
+
+    if outside {
+        shape -= mask::fillet(rounding) >> rotate_z(180) >> back(TILE_D) >> left(TILE_D);
+    } else {
+        shape += mask::fillet(rounding) >> rotate_z(180);
+    }
+
+    let shape = shape >> linear_extrude(TILE_W);
+
+    let cavity = magnet_cavity() >> rotate_y(90) >> up(TILE_W * 0.5);
+    let shift = TILE_D - MAGNET_INSET - (MAGNET_H + TOLERANCE) * 0.5;
+
+    let mut cavities = &cavity >> right(shift) >> back(TILE_D * 0.5);
+    cavities += &cavity >> fwd(shift) >> left(TILE_D * 0.5);
+
+    shape - cavities
+}
+
 fn tile_square_loop() -> Object {
     let decorations = svg!("loop.svg") >> linear_extrude(0.1);
     let decorations =
@@ -74,8 +217,10 @@ fn tile_square_loop() -> Object {
 }
 
 fn tile_window() -> Object {
-    let mut base = tile_square();
+    window(tile_square())
+}
 
+fn window(mut base: Object) -> Object {
     let mut cutouts = pane() >> scale([1.1, 1.1, 1.1]);
 
     let cutout_w = 9.0;
@@ -192,13 +337,47 @@ fn tile_door() -> Object {
     door - decoration_cavities
 }
 
+fn tile_panel(w: i32, h: i32, decorate: impl Fn([i32; 2], Object) -> Object) -> Object {
+    let cells: Vec<_> = (0..h).flat_map(|y| (0..w).map(move |x| [x, y])).collect();
+    tile_panel_cells(&cells, decorate)
+}
+
+fn tile_panel_l(w: i32, h: i32, arm: i32, decorate: impl Fn([i32; 2], Object) -> Object) -> Object {
+    let cells: Vec<_> = (0..h)
+        .flat_map(|y| (0..w).map(move |x| [x, y]))
+        .filter(|&[x, y]| x < arm || y < arm)
+        .collect();
+    tile_panel_cells(&cells, decorate)
+}
+
+fn tile_panel_cells(cells: &[[i32; 2]], decorate: impl Fn([i32; 2], Object) -> Object) -> Object {
+    let mut panel = empty().into_object();
+
+    for &[x, y] in cells {
+        let cavities = [
+            !cells.contains(&[x, y + 1]),
+            !cells.contains(&[x + 1, y]),
+            !cells.contains(&[x, y - 1]),
+            !cells.contains(&[x - 1, y]),
+        ];
+
+        let tile = decorate([x, y], tile_square_cavities(cavities));
+
+        panel += tile >> right(x as f64 * TILE_W) >> fwd(y as f64 * TILE_W);
+    }
+
+    panel
+}
+
 fn tile_decoration_mask() -> Object {
     cube([TILE_W, TILE_W, TILE_D]).center(true).up(TILE_D * 0.5)
 }
 
 fn tile_roof() -> Object {
-    let mut tile = tile_square();
+    tile_square() + roof_shingles()
+}
 
+fn roof_shingles() -> Object {
     let shingles = {
         let radius = TILE_W * 0.5 / 3.0;
         let mut shingle = circle(radius) - circle(radius - 1.0);
@@ -223,9 +402,7 @@ fn tile_roof() -> Object {
         rows & tile_decoration_mask()
     };
 
-    tile += shingles >> up(TILE_D * 0.5);
-
-    tile
+    shingles >> up(TILE_D * 0.5)
 }
 
 fn tile_icicle() -> Object {
@@ -288,13 +465,18 @@ fn wreath() -> Object {
     wreath - cavity
 }
 
-fn gumdrop() -> Object {
-    let base =
-        svg!("gumdrop.svg") >> mirror([1, 0, 0]) >> right(13.14) >> rotate_extrude() >> scale(0.4);
+fn art(file: &str) -> Svg {
+    common::svg(format!("{}/src/{file}", env!("CARGO_MANIFEST_DIR")))
+}
 
-    let cavity = c_magnet_cavity() >> up(2.0);
+fn gumdrop() -> Object {
+    // the artwork is the left half of the profile, standing on its base with the axis along
+    // its right edge
+    let art = art("gumdrop.svg").trim();
+    let [w, h] = art.fitted_size().map(|v| v * GUMDROP_SCALE);
+    let profile = art.width(w).into_object() >> mirror([1, 0, 0]) >> right(w);
 
-    base - cavity
+    Accessory::profile(profile, h).build()
 }
 
 fn snowman_ball(size: f64) -> Object {
@@ -303,7 +485,7 @@ fn snowman_ball(size: f64) -> Object {
     let base = cylinder(height, size * 0.5 - 1.0)
         .center(true)
         .into_object();
-    let base = base.minkowski(sphere(2.0));
+    let base = base.minkowski(sphere(SNOWMAN_ROUNDING));
 
     let mut cavities = empty().into_object();
     cavities += c_magnet_cavity() >> up((height * 0.5) - 1.0);
@@ -325,6 +507,363 @@ fn snowman_hat() -> Object {
     hat
 }
 
+fn snowman_ball_height(size: f64) -> f64 {
+    size * 0.75 + SNOWMAN_ROUNDING * 2.0
+}
+
+fn snowman_ball_radius(size: f64) -> f64 {
+    size * 0.5 - 1.0 + SNOWMAN_ROUNDING
+}
+
+/// A hole for a peg, pointing along +z with its mouth at the origin
+fn snowman_peg_hole() -> Object {
+    let depth = SNOWMAN_PEG_DEPTH + 0.5;
+    cylinder(depth + 1.0, SNOWMAN_PEG_R + SNOWMAN_PEG_CLEARANCE) >> down(depth)
+}
+
+fn snowman_nose(length: f64) -> Object {
+    let radius = SNOWMAN_PEG_R + 1.0;
+    let carrot = polygon([[0.0, 0.0], [radius, 0.0], [0.0, length]]) >> rotate_extrude();
+
+    carrot + (cylinder(SNOWMAN_PEG_DEPTH, SNOWMAN_PEG_R) >> down(SNOWMAN_PEG_DEPTH))
+}
+
+/// A twig arm lying flat, with its peg pointing along -x
+fn snowman_arm(length: f64) -> Object {
+    let thickness = SNOWMAN_PEG_R * 2.4;
+
+    let stick = square([length, thickness]).center(true) >> right(length * 0.5);
+    let twig = square([length * 0.35, thickness * 0.8]).center(true)
+        >> right(length * 0.175)
+        >> rotate_z(35)
+        >> right(length * 0.6);
+
+    let arm = (stick + twig) >> linear_extrude(thickness).center(true);
+
+    arm + (cylinder(SNOWMAN_PEG_DEPTH, SNOWMAN_PEG_R) >> rotate_y(-90))
+}
+
+fn snowman_scarf(inner_r: f64) -> Object {
+    let (width, height) = (2.0, 3.0);
+
+    let ring = square([width, height]) >> right(inner_r) >> rotate_extrude();
+    let tail = cube([6.0, 4.0, height]) >> right(inner_r + width - 0.5) >> back(2.0);
+
+    ring + tail
+}
+
+struct Snowman {
+    name: &'static str,
+    height: f64,
+    nose: bool,
+    arms: bool,
+    scarf: bool,
+}
+
+impl Default for Snowman {
+    fn default() -> Self {
+        Self {
+            name: "snowman",
+            height: 48.5,
+            nose: true,
+            arms: true,
+            scarf: true,
+        }
+    }
+}
+
+impl Snowman {
+    /// Ball sizes from the bottom up, in a 4:3:2 ratio that fills the height under the hat
+    fn sizes(&self) -> [f64; 3] {
+        let balls = self.height - SNOWMAN_HAT_H - SNOWMAN_ROUNDING * 2.0 * 3.0;
+        let unit = balls / (0.75 * 9.0);
+
+        let name = self.name;
+        let head = unit * 2.0;
+        let magnet_r = C_MAGNET_W * 0.5 + 0.85;
+        assert!(
+            snowman_ball_radius(head) >= magnet_r + 1.0,
+            "{name}: a {:.1}mm snowman is too short to hold its magnets",
+            self.height
+        );
+
+        [unit * 4.0, unit * 3.0, head]
+    }
+
+    fn ball(&self, i: usize) -> Object {
+        let sizes = self.sizes();
+        let size = sizes[i];
+        let radius = snowman_ball_radius(size);
+
+        let mut ball = snowman_ball(size);
+
+        if i == 1 && self.arms {
+            ball -= snowman_peg_hole() >> rotate_y(90) >> right(radius);
+            ball -= snowman_peg_hole() >> rotate_y(-90) >> left(radius);
+        }
+
+        if i == 2 && self.nose {
+            ball -= snowman_peg_hole() >> rotate_x(-90) >> fwd(radius);
+        }
+
+        ball
+    }
+
+    fn nose(&self) -> Object {
+        snowman_nose(self.sizes()[2])
+    }
+
+    fn arm(&self) -> Object {
+        snowman_arm(self.sizes()[1] * 1.5)
+    }
+
+    fn scarf(&self) -> Object {
+        let [_, body, head] = self.sizes();
+        let inner_r = snowman_ball_radius(head) + 0.5;
+
+        assert!(
+            inner_r < snowman_ball_radius(body) - 1.0,
+            "{}: the scarf would slide past the body",
+            self.name
+        );
+
+        snowman_scarf(inner_r)
+    }
+
+    fn parts(&self) -> Vec<(Object, String)> {
+        let name = self.name;
+
+        let mut out = ["large", "medium", "small"]
+            .into_iter()
+            .enumerate()
+            .map(|(i, size)| (self.ball(i), format!("{name}_{size}")))
+            .collect::<Vec<_>>();
+
+        out.push((snowman_hat(), format!("{name}_hat")));
+
+        if self.nose {
+            out.push((self.nose(), format!("{name}_nose")));
+        }
+        if self.arms {
+            out.push((self.arm(), format!("{name}_arm")));
+        }
+        if self.scarf {
+            out.push((self.scarf(), format!("{name}_scarf")));
+        }
+
+        out
+    }
+
+    fn preview(&self) -> Object {
+        let sizes = self.sizes();
+
+        let mut stack = empty().into_object();
+        let mut z = 0.0;
+        let mut centers = [0.0; 3];
+
+        for (i, size) in sizes.into_iter().enumerate() {
+            let height = snowman_ball_height(size);
+            centers[i] = z + height * 0.5;
+            stack += self.ball(i) >> up(centers[i]);
+            z += height;
+        }
+
+        let neck = z - snowman_ball_height(sizes[2]);
+
+        stack += snowman_hat() >> up(z + 0.5);
+
+        if self.nose {
+            let radius = snowman_ball_radius(sizes[2]);
+            stack += self.nose() >> rotate_x(-90) >> fwd(radius) >> up(centers[2]);
+        }
+        if self.arms {
+            let radius = snowman_ball_radius(sizes[1]);
+            let arm = self.arm() >> up(centers[1]);
+            stack += &arm >> right(radius);
+            stack += &arm >> mirror([1, 0, 0]) >> left(radius);
+        }
+        if self.scarf {
+            stack += self.scarf() >> up(neck);
+        }
+
+        stack
+    }
+}
+
+enum AccessoryShape {
+    /// Half profile with the axis along y, revolved into a solid of the given height
+    Profile { profile: Object<2>, height: f64 },
+    /// Outline extruded to the given thickness with its edges rounded, which grows it by `rounding`
+    Silhouette {
+        outline: Object<2>,
+        thickness: f64,
+        rounding: f64,
+    },
+}
+
+/// A decoration that sits flat against a tile and holds on with a `c_magnet_cavity`.
+///
+/// The finished part is moved so the magnet sits on the origin, with the back face at z = 0.
+struct Accessory {
+    shape: AccessoryShape,
+    magnet: [f64; 2],
+}
+
+impl Accessory {
+    fn profile(profile: Object<2>, height: f64) -> Self {
+        Self {
+            shape: AccessoryShape::Profile { profile, height },
+            magnet: [0.0; 2],
+        }
+    }
+
+    fn silhouette(outline: Object<2>, thickness: f64) -> Self {
+        Self {
+            shape: AccessoryShape::Silhouette {
+                outline,
+                thickness,
+                rounding: ACCESSORY_ROUNDING,
+            },
+            magnet: [0.0; 2],
+        }
+    }
+
+    /// A silhouette of SVG artwork, with the magnet as near the middle of the artwork as it
+    /// fits with a floor all round
+    fn artwork(file: &str, thickness: f64) -> Self {
+        let art = art(file);
+        let outlines = art.outlines().to_vec();
+        let [min, max] = art.bounds();
+        let middle = [(min[0] + max[0]) * 0.5, (min[1] + max[1]) * 0.5];
+        let needed = C_MAGNET_CAVITY_R + ACCESSORY_FLOOR;
+
+        let step = 0.1;
+        let [nx, ny] = [0, 1].map(|i| ((max[i] - min[i]) / step) as usize);
+        let from_middle = |[x, y]: &[f64; 2]| (x - middle[0]).hypot(y - middle[1]);
+
+        let magnet = (0..=nx)
+            .flat_map(|i| {
+                (0..=ny).map(move |j| [min[0] + i as f64 * step, min[1] + j as f64 * step])
+            })
+            .filter(|point| room(&outlines, *point) >= needed)
+            .min_by(|a, b| from_middle(a).total_cmp(&from_middle(b)))
+            .unwrap_or_else(|| {
+                panic!("{file} has no spot {needed:.2}mm from its edges for the magnet")
+            });
+
+        Self {
+            magnet,
+            ..Self::silhouette(art.into_object(), thickness)
+        }
+    }
+
+    fn rounding(mut self, rounding: f64) -> Self {
+        if let AccessoryShape::Silhouette { rounding: r, .. } = &mut self.shape {
+            *r = rounding;
+        }
+        self
+    }
+
+    fn height(&self) -> f64 {
+        match self.shape {
+            AccessoryShape::Profile { height, .. } => height,
+            AccessoryShape::Silhouette { thickness, .. } => thickness,
+        }
+    }
+
+    fn build(&self) -> Object {
+        let cavity_h = C_MAGNET_H + TOLERANCE;
+        let height = self.height();
+
+        assert!(
+            height >= cavity_h + ACCESSORY_FLOOR * 2.0,
+            "a {height}mm accessory can't hold a {cavity_h}mm magnet with {ACCESSORY_FLOOR}mm floors"
+        );
+
+        let body = match &self.shape {
+            AccessoryShape::Profile { profile, .. } => profile.clone() >> rotate_extrude(),
+            AccessoryShape::Silhouette {
+                outline,
+                thickness,
+                rounding,
+            } => {
+                let core = thickness - rounding * 2.0;
+                assert!(
+                    core > 0.0,
+                    "rounding {rounding} is too large for {thickness}mm"
+                );
+
+                let body = outline.clone() >> linear_extrude(core);
+                body.minkowski(sphere(*rounding)) >> up(*rounding)
+            }
+        };
+
+        let [x, y] = self.magnet;
+        let body = body >> translate([-x, -y, 0.0]);
+
+        body - (c_magnet_cavity() >> up(ACCESSORY_FLOOR + cavity_h * 0.5))
+    }
+}
+
+/// How far `point` is from the nearest edge of the artwork, or 0 outside it
+fn room(outlines: &[Vec<[f64; 2]>], [x, y]: [f64; 2]) -> f64 {
+    let mut inside = false;
+    let mut nearest = f64::MAX;
+
+    for outline in outlines {
+        for (i, &a) in outline.iter().enumerate() {
+            let b = outline[(i + 1) % outline.len()];
+            if (a[1] > y) != (b[1] > y) && x < a[0] + (y - a[1]) * (b[0] - a[0]) / (b[1] - a[1]) {
+                inside = !inside;
+            }
+
+            let [dx, dy] = [b[0] - a[0], b[1] - a[1]];
+            let len = dx * dx + dy * dy;
+            let t = if len > 0.0 {
+                (((x - a[0]) * dx + (y - a[1]) * dy) / len).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            nearest = nearest.min((a[0] + dx * t - x).hypot(a[1] + dy * t - y));
+        }
+    }
+
+    if inside {
+        nearest
+    } else {
+        0.0
+    }
+}
+
+fn candy_cane() -> Object {
+    Accessory::artwork("candy-cane.svg", 5.0).build()
+}
+
+fn lollipop() -> Object {
+    let radius = 10.0;
+    let stick = square([3.0, radius * 2.0]).center(true) >> back(radius);
+
+    Accessory::silhouette(circle(radius) + stick, 5.0)
+        .rounding(1.5)
+        .build()
+}
+
+fn star() -> Object {
+    let points = (0..10)
+        .map(|i| {
+            let r = if i % 2 == 0 { 14.0 } else { 6.5 };
+            let angle = (i as f64 * 36.0).to_radians();
+            [r * angle.sin(), r * angle.cos()]
+        })
+        .collect::<Vec<_>>();
+
+    Accessory::silhouette(polygon(points).into_object(), 4.5).build()
+}
+
+fn bow() -> Object {
+    Accessory::artwork("bow.svg", 5.0).build()
+}
+
 fn roof_dims(diagonal: f64, horizontal: f64) -> [f64; 3] {
     let w = TILE_W * horizontal;
     let h = {
@@ -336,63 +875,217 @@ fn roof_dims(diagonal: f64, horizontal: f64) -> [f64; 3] {
     [w, h, a]
 }
 
-fn tile_triangle(diagonal: f64, horizontal: f64) -> Object {
-    let [w, h, angle] = roof_dims(diagonal, horizontal);
-    let tile = polygon([[0.0, 0.0], [w * 0.5, h], [w, 0.0]]).into_object();
+fn roof_pitch(pitch: f64, span: f64) -> [f64; 3] {
+    let w = TILE_W * span;
+    let h = w * 0.5 * pitch.to_radians().tan();
+    [w, h, pitch]
+}
 
-    let tile = tile >> linear_extrude(TILE_D) >> down(TILE_D * 0.5);
+fn roof_slope([w, h, _angle]: [f64; 3]) -> f64 {
+    (w * 0.5).hypot(h)
+}
 
-    let cavities = {
-        let shift = TOLERANCE * 0.175;
+#[derive(Clone, Copy, Debug)]
+enum DecorationPocket {
+    Centroid,
+    Slope(f64),
+}
 
-        let center_cavity = |obj: Object| {
-            obj >> right(MAGNET_PLACEMENT + MAGNET_W - shift) >> fwd(MAGNET_H - shift)
-        };
+#[derive(Clone, Copy, Debug)]
+struct Pocket {
+    x: f64,
+    y: f64,
+    angle: f64,
+}
 
-        let bottom_cavity = center_cavity(magnet_cavity());
+impl Pocket {
+    fn cavity(&self) -> Object {
+        magnet_cavity() >> rotate_z(self.angle) >> translate([self.x, self.y, 0.0])
+    }
 
-        let mut row = bottom_cavity.clone();
-        row += &bottom_cavity >> right(TILE_W);
-        row += &bottom_cavity >> right(TILE_W * 2.0);
+    fn axes(&self) -> [[f64; 2]; 2] {
+        let (sin, cos) = self.angle.to_radians().sin_cos();
+        [[cos, sin], [-sin, cos]]
+    }
 
-        let angled_c = &row >> mirror([0, 1, 0]) >> rotate([0.0, 0.0, angle]);
+    fn corners(&self) -> [[f64; 2]; 4] {
+        let [u, v] = self.axes();
+        let l = (MAGNET_L + TOLERANCE) * 0.5;
+        let h = (MAGNET_H + TOLERANCE) * 0.5;
+        [[-l, -h], [l, -h], [l, h], [-l, h]]
+            .map(|[a, b]| [self.x + u[0] * a + v[0] * b, self.y + u[1] * a + v[1] * b])
+    }
 
-        let mut cavities = if diagonal < 3.0 && horizontal > 1.0 {
-            &bottom_cavity >> right(TILE_W)
-        } else {
-            row
+    fn collides(&self, other: &Self) -> bool {
+        let a = self.corners();
+        let b = other.corners();
+
+        let project = |corners: &[[f64; 2]; 4], axis: [f64; 2]| {
+            corners
+                .iter()
+                .map(|[x, y]| x * axis[0] + y * axis[1])
+                .fold((f64::MAX, f64::MIN), |(min, max), v| {
+                    (min.min(v), max.max(v))
+                })
         };
-        cavities += &angled_c;
-        cavities += &angled_c >> mirror([1, 0, 0]) >> right(w);
 
-        // TODO decoration cavities
+        self.axes().into_iter().chain(other.axes()).all(|axis| {
+            let (a_min, a_max) = project(&a, axis);
+            let (b_min, b_max) = project(&b, axis);
+            a_max + DECORATION_CLEARANCE > b_min && b_max + DECORATION_CLEARANCE > a_min
+        })
+    }
+
+    fn mirror(&self, w: f64) -> Self {
+        Self {
+            x: w - self.x,
+            y: self.y,
+            angle: -self.angle,
+        }
+    }
+}
+
+fn triangle_inset([x, y]: [f64; 2], w: f64, angle: f64) -> f64 {
+    let (sin, cos) = angle.to_radians().sin_cos();
+    let left = x * sin - y * cos;
+    let right = (w - x) * sin - y * cos;
+    y.min(left).min(right)
+}
 
-        cavities += center_cavity(&bottom_cavity >> rotate([0, 0, 90]))
-            >> right(TILE_W + MAGNET_W)
-            >> back(1.0);
+fn triangle_pockets(dims: [f64; 3], decorations: &[DecorationPocket]) -> Vec<Pocket> {
+    let [w, h, angle] = dims;
+    let (sin, cos) = angle.to_radians().sin_cos();
+    let slope_len = roof_slope(dims);
 
-        cavities
+    let shift = TOLERANCE * 0.175;
+    let center_x = MAGNET_PLACEMENT + MAGNET_W - shift;
+    let center_y = MAGNET_H - shift;
+
+    let inside = |pocket: &Pocket, inset: f64| {
+        pocket
+            .corners()
+            .into_iter()
+            .all(|corner| triangle_inset(corner, w, angle) >= inset)
     };
 
+    let slope = |along: f64, inward: f64| Pocket {
+        x: along * cos + inward * sin,
+        y: along * sin - inward * cos,
+        angle,
+    };
+
+    let mut pockets = vec![];
+
+    for i in 0..(slope_len / TILE_W).ceil() as usize {
+        let pocket = slope(center_x + TILE_W * i as f64, center_y);
+        if inside(&pocket, 0.0) {
+            pockets.push(pocket);
+            pockets.push(pocket.mirror(w));
+        }
+    }
+
+    // shallow gables leave no room for the outer bottom cavities next to the slope ones
+    let bottom = (0..(w / TILE_W).ceil() as usize).map(|i| Pocket {
+        x: center_x + TILE_W * i as f64,
+        y: center_y,
+        angle: 0.0,
+    });
+
+    // upright in the middle of the base, under the peak
+    let center = Pocket {
+        x: w * 0.5,
+        y: center_x + center_y - 1.0,
+        angle: 90.0,
+    };
+
+    for pocket in bottom.chain([center]) {
+        if inside(&pocket, 0.0) && !pockets.iter().any(|other| pocket.collides(other)) {
+            pockets.push(pocket);
+        }
+    }
+
+    // slide each decoration away from its preferred spot until it clears the edge cavities
+    let offsets = || (0..200).flat_map(|i| [i as f64 * 0.25, i as f64 * -0.25]);
+
+    for decoration in decorations {
+        let candidates: Vec<Vec<Pocket>> = match *decoration {
+            DecorationPocket::Centroid => offsets()
+                .flat_map(|offset| {
+                    [0.0, 90.0].map(|angle| {
+                        vec![Pocket {
+                            x: w * 0.5,
+                            y: h / 3.0 + offset,
+                            angle,
+                        }]
+                    })
+                })
+                .collect(),
+            DecorationPocket::Slope(t) => {
+                let inward = center_y + MAGNET_H + TOLERANCE + DECORATION_CLEARANCE;
+                offsets()
+                    .map(|offset| {
+                        let pocket = slope(slope_len * t + offset, inward);
+                        vec![pocket, pocket.mirror(w)]
+                    })
+                    .collect()
+            }
+        };
+
+        let placed = candidates
+            .into_iter()
+            .find(|candidate| {
+                candidate.iter().enumerate().all(|(idx, pocket)| {
+                    pocket
+                        .corners()
+                        .into_iter()
+                        .all(|corner| triangle_inset(corner, w, angle) >= DECORATION_WALL)
+                        && pockets
+                            .iter()
+                            .chain(&candidate[..idx])
+                            .all(|other| !pocket.collides(other))
+                })
+            })
+            .unwrap_or_else(|| panic!("{decoration:?} does not fit in a {w:.2}x{h:.2} triangle"));
+
+        pockets.extend(placed);
+    }
+
+    pockets
+}
+
+fn tile_triangle(dims: [f64; 3], decorations: &[DecorationPocket]) -> Object {
+    let [w, h, _angle] = dims;
+    let tile = polygon([[0.0, 0.0], [w * 0.5, h], [w, 0.0]]).into_object();
+
+    let tile = tile >> linear_extrude(TILE_D) >> down(TILE_D * 0.5);
+
+    let mut cavities = empty().into_object();
+
+    for pocket in triangle_pockets(dims, decorations) {
+        cavities += pocket.cavity();
+    }
+
     tile - cavities
 }
 
-fn roof_fascia(diagonal: f64, horizontal: f64) -> Object {
-    let mut out = roof_ridge(diagonal, horizontal, false);
+fn roof_fascia(dims: [f64; 3]) -> Object {
+    let mut out = roof_ridge(dims, false);
 
     out &= cube([TILE_W, TILE_W, TILE_W]) >> back(TILE_W) >> down(TILE_W * 0.5);
 
     out >> rotate_y(-90)
 }
 
-fn roof_ridge(diagonal: f64, horizontal: f64, flat: bool) -> Object {
-    let [w, h, _angle] = roof_dims(diagonal, horizontal);
-    let roof = polygon([[0.0, 0.0], [w * 0.5, h], [w, 0.0]])
-        >> mirror([0, 1, 0])
-        >> fwd(h)
-        >> left(w * 0.5)
-        >> scale([0.75, 1.0, 1.0]);
+fn ridge_wedge(half_angle: f64) -> Object<2> {
+    let h = TILE_D * 2.0;
+    let w = h * half_angle.to_radians().tan() * 2.0;
+    polygon([[0.0, 0.0], [w * 0.5, h], [w, 0.0]]) >> mirror([0, 1, 0]) >> fwd(h) >> left(w * 0.5)
+}
+
+fn roof_ridge(dims: [f64; 3], flat: bool) -> Object {
+    let [_w, _h, angle] = dims;
 
+    // the square-cut ends of two roof tiles leave a gap of twice the pitch above the peak
     let top = {
         let mut shape = circle(TILE_D + 0.5).into_object();
 
@@ -400,13 +1093,14 @@ fn roof_ridge(diagonal: f64, horizontal: f64, flat: bool) -> Object {
             shape &= square((TILE_D * 2.0) * 0.99).center(true);
         }
 
-        (roof.clone() & shape) >> linear_extrude(TILE_W)
+        (ridge_wedge(angle) & shape) >> linear_extrude(TILE_W)
     };
 
+    // the key under the peak can't be wider than the gap between the tiles' inner faces
     let bottom = {
         let shape = circle(3.0).into_object();
 
-        (roof.clone() & shape)
+        (ridge_wedge(angle.min(90.0 - angle)) & shape)
             >> mirror([0, 1, 0])
             >> linear_extrude(TILE_W - TILE_D * 2.0)
             >> fwd(0.35)
@@ -418,6 +1112,72 @@ fn roof_ridge(diagonal: f64, horizontal: f64, flat: bool) -> Object {
     out >> rotate([90.0, 180.0, 0.0])
 }
 
+fn tile_roof_trim(length: f64) -> Object {
+    assert!(
+        length >= ROOF_TRIM_MIN,
+        "a {length:.2}mm roof trim is too short to hold a magnet (min {ROOF_TRIM_MIN:.2}mm)"
+    );
+
+    let base = cube([TILE_W, length, TILE_D]).center(true).into_object();
+
+    let placement = (length - MAGNET_H - TOLERANCE) * 0.5 - MAGNET_INSET;
+    let mut cavities = magnet_cavity() >> fwd(placement);
+
+    if length >= MAGNET_L + TOLERANCE + MAGNET_INSET * 2.0 {
+        cavities += magnet_cavity() >> rotate([0.0, 0.0, 90.0]) >> right(MAGNET_PLACEMENT);
+        cavities += magnet_cavity() >> rotate([0.0, 0.0, 90.0]) >> left(MAGNET_PLACEMENT);
+    }
+
+    let shingles = roof_shingles() & cube([TILE_W, length, TILE_D * 4.0]).center(true);
+
+    base - cavities + shingles
+}
+
+fn roof_family(pitch: f64, span: f64) -> Vec<(Object, String)> {
+    assert!(
+        pitch > 0.0 && pitch < 75.0,
+        "roof pitch must be between 0° and 75°, got {pitch}°"
+    );
+
+    let dims = roof_pitch(pitch, span);
+    let name = format!("{pitch}deg_{span}");
+
+    let pockets = triangle_pockets(dims, &[]);
+    assert!(
+        pockets.iter().any(|pocket| pocket.angle == pitch),
+        "the {name} gable has no room for slope magnets"
+    );
+    assert!(
+        pockets.iter().any(|pocket| pocket.angle == 0.0),
+        "the {name} gable has no room for bottom magnets"
+    );
+
+    let slope = roof_slope(dims);
+    let rows = ((slope + 1e-6) / TILE_W).floor();
+    let trim = slope - rows * TILE_W;
+
+    // a sliver too short for a magnet is lengthened to overhang the eave instead
+    let trim = if trim > 1e-6 {
+        trim.max(ROOF_TRIM_MIN)
+    } else {
+        trim
+    };
+
+    // each slope is `rows` shingled tiles, finished at the eave by the trim
+    let mut out = vec![
+        (tile_roof(), format!("tile_roof_{name}")),
+        (tile_triangle(dims, &[]), format!("tile_gable_{name}")),
+        (roof_ridge(dims, true), format!("roof_ridge_{name}")),
+        (roof_fascia(dims), format!("roof_fascia_{name}")),
+    ];
+
+    if trim > 1e-6 {
+        out.push((tile_roof_trim(trim), format!("tile_roof_trim_{name}")));
+    }
+
+    out
+}
+
 fn peppermint_base() -> Object {
     let radius = 14.0 * 0.5;
     let circle = circle(radius) >> linear_extrude(0.1);
@@ -474,7 +1234,128 @@ fn filler(chunkiness: f64, has_back: bool) -> Object {
     base >> rotate_x(90)
 }
 
-fn tree_section(radius: f64, include_decoration: bool, n: u32) -> Object {
+#[derive(Clone, Copy, Debug)]
+enum RadiusCurve {
+    Linear(f64),
+    Exponential(f64),
+    Custom(fn(u32) -> f64),
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Tree {
+    name: &'static str,
+    sections: u32,
+    base_radius: f64,
+    curve: RadiusCurve,
+    arms: u32,
+    twist: f64,
+    topper: bool,
+    height: f64,
+}
+
+impl Default for Tree {
+    fn default() -> Self {
+        Self {
+            name: "tree",
+            sections: 13,
+            base_radius: 35.0,
+            curve: RadiusCurve::Linear(2.0),
+            arms: 5,
+            twist: 0.0,
+            topper: false,
+            height: 92.3,
+        }
+    }
+}
+
+impl Tree {
+    fn radius(&self, i: u32) -> f64 {
+        match self.curve {
+            RadiusCurve::Linear(step) => self.base_radius - i as f64 * step,
+            RadiusCurve::Exponential(ratio) => self.base_radius * ratio.powi(i as i32),
+            RadiusCurve::Custom(f) => f(i),
+        }
+    }
+
+    fn section(&self, i: u32) -> Object {
+        let n = i + 1;
+        let twist = self.twist * i as f64;
+        tree_section(self.radius(i), self.arms, twist, n < self.sections, n)
+    }
+
+    fn parts(&self) -> Vec<(Object, String)> {
+        let name = self.name;
+
+        let mut out = (0..self.sections)
+            .map(|i| (self.section(i), format!("{name}_section_{}", i + 1)))
+            .collect::<Vec<_>>();
+
+        if self.topper {
+            out.push((tree_topper(), format!("{name}_topper")));
+        }
+
+        out
+    }
+
+    fn preview(&self) -> Object {
+        let name = self.name;
+        let magnet_r = C_MAGNET_W * 0.5 + 0.85;
+
+        let check_gap = |below: f64, above: f64, n: u32| {
+            let gap = above - below - C_MAGNET_H;
+            assert!(
+                gap <= TREE_MAGNET_MAX_GAP,
+                "{name}: magnets above section {n} are {gap:.2}mm apart"
+            );
+        };
+
+        let mut stack = empty().into_object();
+        let mut z = 0.0;
+        let mut below = None;
+
+        for i in 0..self.sections {
+            let n = i + 1;
+            let radius = self.radius(i);
+
+            // the arm bases meet around the hub, leaving roughly 0.4r plus the rounding
+            let hub = radius * 0.4 + TREE_ROUNDING;
+            assert!(
+                hub >= magnet_r + TREE_HUB_WALL,
+                "{name}: section {n} (r = {radius:.2}) is too small to hold its magnets"
+            );
+
+            let center = z + TREE_ROUNDING;
+
+            if let Some(below) = below {
+                check_gap(below, center + TREE_MAGNET_BOTTOM, i);
+            }
+
+            stack += self.section(i) >> up(center);
+
+            below = Some(center + TREE_MAGNET_TOP);
+            z = center + TREE_FROSTING_TOP;
+        }
+
+        if self.topper {
+            if let Some(below) = below {
+                check_gap(below, z + TREE_TOPPER_MAGNET, self.sections);
+            }
+
+            stack += tree_topper() >> up(z);
+            z += tree_topper_height();
+        }
+
+        assert!(
+            (z - self.height).abs() <= 0.5,
+            "{name}: the stack is {z:.2}mm tall but should be {:.2}mm",
+            self.height
+        );
+
+        stack
+    }
+}
+
+fn tree_section(radius: f64, arms: u32, twist: f64, include_decoration: bool, n: u32) -> Object {
     let l = radius;
     let w = radius * 0.8;
     let h = 3.0;
@@ -489,8 +1370,8 @@ fn tree_section(radius: f64, include_decoration: bool, n: u32) -> Object {
 
         let mut out = empty().into_object();
 
-        for i in 0..5 {
-            let angle = i as f64 / 5.0 * 360.0;
+        for i in 0..arms {
+            let angle = i as f64 / arms as f64 * 360.0 + twist;
             out += &path >> rotate_z(angle);
         }
 
@@ -498,20 +1379,20 @@ fn tree_section(radius: f64, include_decoration: bool, n: u32) -> Object {
     };
 
     let star = pattern(l, w) >> linear_extrude(0.1);
-    let star = star.minkowski(sphere(3.0));
+    let star = star.minkowski(sphere(TREE_ROUNDING));
 
     let frosting = pattern(l * 0.93, w * 0.87) >> linear_extrude(1.0);
-    let frosting = frosting.minkowski(sphere(1.0)) >> up(2.1);
+    let frosting = frosting.minkowski(sphere(1.0)) >> up(TREE_FROSTING_TOP - 2.0);
 
-    let mut cavities = c_magnet_cavity() >> up(h * 0.5 + 0.5);
-    cavities += c_magnet_cavity() >> down(h * 0.5 - 0.3);
+    let mut cavities = c_magnet_cavity() >> up(TREE_MAGNET_TOP);
+    cavities += c_magnet_cavity() >> up(TREE_MAGNET_BOTTOM);
 
     let decoration = {
         let mut out = empty().into_object();
         let leaf = sphere(3.0) >> fwd(radius * 0.7);
 
-        for i in 0..5 {
-            let angle = i as f64 / 5.0 * 360.0;
+        for i in 0..arms {
+            let angle = i as f64 / arms as f64 * 360.0 + twist;
             out += &leaf >> rotate_z(angle);
         }
 
@@ -524,10 +1405,11 @@ fn tree_section(radius: f64, include_decoration: bool, n: u32) -> Object {
         out += decoration;
     }
 
-    let label = text(format!("{n}"))
-        .font("Baloo 2")
+    let label = common::text(format!("{n}"))
+        .font("Baloo 2:style=Bold")
         .halign("center")
         .valign("center")
+        .into_object()
         >> mirror_x()
         >> linear_extrude(0.2)
         >> down(h);
@@ -537,6 +1419,41 @@ fn tree_section(radius: f64, include_decoration: bool, n: u32) -> Object {
     out - cavities
 }
 
+/// The height of the star's middle, with its two lower tips resting on the bed beside the base
+fn tree_topper_star_z() -> f64 {
+    TREE_TOPPER_R * 36f64.to_radians().cos() + TREE_TOPPER_ROUNDING
+}
+
+fn tree_topper_height() -> f64 {
+    tree_topper_star_z() + TREE_TOPPER_R + TREE_TOPPER_ROUNDING
+}
+
+fn tree_topper() -> Object {
+    let points = (0..10)
+        .map(|i| {
+            let r = if i % 2 == 0 {
+                TREE_TOPPER_R
+            } else {
+                TREE_TOPPER_R * 0.45
+            };
+            let angle = (i as f64 * 36.0).to_radians();
+            [r * angle.sin(), r * angle.cos()]
+        })
+        .collect::<Vec<_>>();
+
+    let star = polygon(points) >> linear_extrude(3.0).center(true);
+    let star =
+        star.minkowski(sphere(TREE_TOPPER_ROUNDING)) >> rotate_x(90) >> up(tree_topper_star_z());
+
+    let base = cylinder(TREE_TOPPER_BASE_H, C_MAGNET_W * 0.5 + 2.5);
+
+    let mut topper = star + base;
+
+    topper -= c_magnet_cavity() >> up(TREE_TOPPER_MAGNET);
+
+    topper
+}
+
 fn main() {
     let settings = fragment_count(50).preview(25);
 
@@ -544,26 +1461,61 @@ fn main() {
     // let targets = &[];
 
     let mut out = [
-        (tile_square(), "tile_square"),
+        (tile_label(tile_square()), "tile_square"),
         (tile_square_loop(), "tile_square_loop"),
-        (tile_triangle(1.0, 1.0), "tile_triangle"),
+        (tile_triangle(roof_dims(1.0, 1.0), &[]), "tile_triangle"),
         (tile_window(), "tile_window"),
         (tile_window_icicle(), "tile_window_icicle"),
         (tile_chimney(), "tile_chimney"),
         (tile_door(), "tile_door"),
         (tile_roof(), "tile_roof"),
         (tile_icicle(), "tile_icicle"),
-        (tile_triangle(3.0, 3.0), "tile_gable"),
-        (tile_triangle(2.0, 3.0), "tile_gable_shallow"),
-        (roof_fascia(2.0, 3.0), "roof_fascia"),
-        (roof_ridge(2.0, 3.0, true), "roof_ridge"),
+        (tile_square_bevel([true, false, false, false]), "tile_bevel"),
+        (
+            tile_square_bevel([true, true, false, false]),
+            "tile_bevel_corner",
+        ),
+        (corner_outside(), "corner_outside"),
+        (corner_inside(), "corner_inside"),
+        (tile_panel(2, 2, |_, tile| tile), "panel_2x2"),
+        (
+            tile_panel(
+                3,
+                2,
+                |[x, _], tile| if x == 1 { window(tile) } else { tile },
+            ),
+            "panel_3x2_window",
+        ),
+        (
+            tile_panel(3, 2, |_, tile| tile + roof_shingles()),
+            "panel_roof_3x2",
+        ),
+        (tile_panel_l(3, 3, 1, |_, tile| tile), "panel_l_3x3"),
+        (tile_triangle(roof_dims(3.0, 3.0), &[]), "tile_gable"),
+        (
+            tile_triangle(roof_dims(2.0, 3.0), &[]),
+            "tile_gable_shallow",
+        ),
+        (
+            tile_triangle(
+                roof_dims(3.0, 3.0),
+                &[DecorationPocket::Centroid, DecorationPocket::Slope(0.5)],
+            ),
+            "tile_gable_decorated",
+        ),
+        (
+            tile_triangle(roof_dims(2.0, 3.0), &[DecorationPocket::Centroid]),
+            "tile_gable_shallow_decorated",
+        ),
+        (roof_fascia(roof_dims(2.0, 3.0)), "roof_fascia"),
+        (roof_ridge(roof_dims(2.0, 3.0), true), "roof_ridge"),
         (pane(), "pane"),
         (wreath(), "wreath"),
         (gumdrop(), "gumdrop"),
-        (snowman_ball(16.0), "snowman_large"),
-        (snowman_ball(12.0), "snowman_medium"),
-        (snowman_ball(8.0), "snowman_small"),
-        (snowman_hat(), "snowman_hat"),
+        (candy_cane(), "candy_cane"),
+        (lollipop(), "lollipop"),
+        (star(), "star"),
+        (bow(), "bow"),
         (peppermint(), "peppermint"),
         (peppermint_swirl(), "peppermint_swirl"),
         (filler(2.15, true), "filler"),
@@ -573,15 +1525,30 @@ fn main() {
     .map(|(v, name)| (v, name.to_string()))
     .collect::<Vec<_>>();
 
-    let tree_sections = 13;
-    for i in 0..tree_sections {
-        let n = i + 1;
-        let scale = 2.0;
-        let radius = 35.0 - (i as f64 * scale);
-        out.push((
-            tree_section(radius, n < tree_sections, n),
-            format!("tree_section_{n}"),
-        ));
+    for (pitch, span) in [(30.0, 3.0), (40.0, 3.0), (55.0, 3.0)] {
+        out.extend(roof_family(pitch, span));
+    }
+
+    let snowman = Snowman::default();
+    out.extend(snowman.parts());
+    out.push((snowman.preview(), format!("{}_preview", snowman.name)));
+
+    let trees = [
+        Tree::default(),
+        Tree {
+            name: "tree_twisted",
+            curve: RadiusCurve::Exponential(0.9),
+            arms: 6,
+            twist: 15.0,
+            topper: true,
+            height: 115.0,
+            ..Default::default()
+        },
+    ];
+
+    for tree in trees {
+        out.extend(tree.parts());
+        out.push((tree.preview(), format!("{}_preview", tree.name)));
     }
 
     out.par_iter().for_each(|(object, name)| {
@@ -1,79 +1,307 @@
+use common::{svg, text};
 use rsolid::*;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
-const BAND_W: f64 = 10.25;
-const BAND_T: f64 = 1.04;
-const CLIP_T: f64 = 1.5;
-const CLIP_W: f64 = BAND_W + CLIP_T * 2.0;
-const CLIP_SPACING: f64 = 1.5;
+/// Room either side of the strap's width
+const SIDE_CLEARANCE: f64 = 0.25;
+/// Room over the strap's thickness
+const GAP_CLEARANCE: f64 = 0.46;
+const FILLET_R: f64 = 0.3;
+/// How much of the strap's thickness a snap-open keeper's side opening spans, so the strap
+/// squeezes past its lips
+const SNAP_RATIO: f64 = 0.8;
 
-fn clip() -> Object {
-    let clip = cube([CLIP_W, CLIP_W, CLIP_T]).center(true);
+/// Where emblems are read from, unless `EMBLEMS` points somewhere else
+const EMBLEMS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/emblems");
+const EMBLEMS_ENV: &str = "EMBLEMS";
+/// The clear space left around an emblem on the clip face
+const EMBLEM_MARGIN: f64 = 0.8;
+const EMBLEM_T: f64 = 2.0;
+/// How far an emblem stands proud of the keeper face
+const EMBLEM_RAISE: f64 = 0.75;
+const INITIALS_FONT: &str = "DejaVu Sans:style=Bold";
 
-    let top = clip >> down(CLIP_T);
-    let bottom = clip >> up(CLIP_T);
-    let right = cube([CLIP_W, CLIP_T, CLIP_SPACING * 3.0]).center(true) >> fwd(BAND_W * 0.5 + 1.0);
-    let left = &right >> mirror([0, 1, 0]);
+#[derive(Clone, Copy, Debug)]
+struct Strap {
+    width: f64,
+    thickness: f64,
+}
+
+const GOGGLE_BAND: Strap = Strap {
+    width: 10.25,
+    thickness: 1.04,
+};
+const BACKPACK_WEBBING: Strap = Strap {
+    width: 25.0,
+    thickness: 1.6,
+};
+const WATCH_BAND: Strap = Strap {
+    width: 20.0,
+    thickness: 2.5,
+};
+const LANYARD: Strap = Strap {
+    width: 15.0,
+    thickness: 1.2,
+};
+
+#[derive(Clone, Copy, Debug)]
+enum Style {
+    Closed,
+    /// One side wall is split at every strap so the keeper snaps on without threading
+    SnapOpen,
+}
+
+/// A slide that holds one or more straps stacked flat on each other
+struct Keeper {
+    name: String,
+    strap: Strap,
+    straps: usize,
+    style: Style,
+    wall: f64,
+    emblem: Option<Emblem>,
+}
+
+impl Default for Keeper {
+    fn default() -> Self {
+        Self {
+            name: "goggle_clip".to_string(),
+            strap: GOGGLE_BAND,
+            straps: 1,
+            style: Style::Closed,
+            wall: 1.5,
+            emblem: None,
+        }
+    }
+}
+
+impl Keeper {
+    /// How far the keeper runs along the strap
+    fn length(&self) -> f64 {
+        self.strap.width + self.wall * 2.0
+    }
 
-    let shape = top + bottom + right + left;
+    fn gap(&self) -> f64 {
+        self.strap.thickness + GAP_CLEARANCE
+    }
 
-    let mask = {
-        let shift = 0.001;
-        let depth = CLIP_SPACING * 1.5 + shift;
+    /// The outside across the strap and through the stack
+    fn size(&self) -> [f64; 2] {
+        let n = self.straps as f64;
+        [
+            self.strap.width + SIDE_CLEARANCE * 2.0 + self.wall * 2.0,
+            self.gap() * n + self.wall * (n + 1.0),
+        ]
+    }
 
-        let top = mask::face::cube(
-            CLIP_W + shift,
-            CLIP_W + CLIP_SPACING * 0.335 + shift,
-            mask::fillet(0.3),
-        ) >> up(depth);
+    /// The middle of each strap's gap
+    fn gaps(&self) -> Vec<f64> {
+        let [_, h] = self.size();
+        (0..self.straps)
+            .map(|i| -h * 0.5 + self.wall + self.gap() * 0.5 + (self.gap() + self.wall) * i as f64)
+            .collect()
+    }
 
-        let bottom = &top >> mirror([0, 0, 1]);
+    fn body(&self) -> Object {
+        assert!(self.straps > 0, "{} holds no straps", self.name);
 
-        let right = mask::face::cube(CLIP_W + shift, depth * 2.0, mask::fillet(0.3))
-            >> rotate_x(-90)
-            >> fwd(CLIP_W * 0.5 + 0.25 + shift);
+        let length = self.length();
+        let [w, h] = self.size();
+        let wall = self.wall;
 
+        let mut shape = empty().into_object();
+
+        for i in 0..=self.straps {
+            let z = -h * 0.5 + wall * 0.5 + (self.gap() + wall) * i as f64;
+            shape += cube([length, w, wall]).center(true) >> up(z);
+        }
+
+        let right = cube([length, wall, h]).center(true) >> fwd((w - wall) * 0.5);
         let left = &right >> mirror([0, 1, 0]);
+        shape += right + left;
+
+        if let Style::SnapOpen = self.style {
+            for z in self.gaps() {
+                let opening = self.strap.thickness * SNAP_RATIO;
+                shape -= cube([length + 2.0, wall + 2.0, opening]).center(true)
+                    >> fwd((w - wall) * 0.5)
+                    >> up(z);
+            }
+        }
+
+        let mask = {
+            let shift = 0.001;
+
+            let top = mask::face::cube(length + shift, w + shift, mask::fillet(FILLET_R))
+                >> up(h * 0.5 + shift);
+
+            let bottom = &top >> mirror([0, 0, 1]);
+
+            let right = mask::face::cube(length + shift, h + shift * 2.0, mask::fillet(FILLET_R))
+                >> rotate_x(-90)
+                >> fwd(w * 0.5 + shift);
+
+            let left = &right >> mirror([0, 1, 0]);
 
-        top + bottom + right + left
-    };
+            top + bottom + right + left
+        };
 
-    shape - mask.dbg()
+        shape - mask
+    }
+
+    /// The straps running through, for previews
+    fn strap_preview(&self) -> Object {
+        let mut out = empty().into_object();
+        for z in self.gaps() {
+            out += cube([200.0, self.strap.width, self.strap.thickness]).center(true) >> up(z);
+        }
+        out
+    }
+
+    fn model(&self) -> Object {
+        let mut out = self.strap_preview().bg() + self.body();
+
+        if let Some(emblem) = &self.emblem {
+            let [w, h] = self.size();
+            let face = self.length().min(w);
+            out += emblem.shape(face - EMBLEM_MARGIN * 2.0)
+                >> linear_extrude(EMBLEM_T)
+                >> up(h * 0.5 + EMBLEM_RAISE - EMBLEM_T);
+        }
+
+        out
+    }
 }
 
-fn band() -> Object {
-    cube([200.0, BAND_W, BAND_T]).center(true).into()
+/// Something raised on the clip face
+enum Emblem {
+    Svg(PathBuf),
+    Initials(String),
 }
 
-fn star() -> Object {
-    import::svg(concat!(env!("CARGO_MANIFEST_DIR"), "/src/star.svg"))
-        >> left(105)
-        >> back(105)
-        >> scale([0.05, 0.05, 1.0])
-        >> linear_extrude(2.0)
-        >> up(1.0)
+impl Emblem {
+    /// The emblem centered on the origin and scaled to fit a square of `size`
+    fn shape(&self, size: f64) -> Object<2> {
+        match self {
+            Self::Svg(path) => svg(path).trim().fit([size; 2]).center().into_object(),
+            Self::Initials(initials) => {
+                let lettering = || {
+                    text(initials)
+                        .font(INITIALS_FONT)
+                        .halign("center")
+                        .valign("center")
+                };
+
+                let (min, max) = lettering().size(1.0).contours().iter().flatten().fold(
+                    ([f64::MAX; 2], [f64::MIN; 2]),
+                    |(min, max), [x, y]| {
+                        (
+                            [min[0].min(*x), min[1].min(*y)],
+                            [max[0].max(*x), max[1].max(*y)],
+                        )
+                    },
+                );
+                let fit = (size / (max[0] - min[0])).min(size / (max[1] - min[1]));
+
+                lettering().size(fit).into_object()
+            }
+        }
+    }
 }
 
-fn heart() -> Object {
-    import::svg(concat!(env!("CARGO_MANIFEST_DIR"), "/src/heart.svg"))
-        >> left(105)
-        >> back(105)
-        >> scale([0.05, 0.05, 1.0])
-        >> linear_extrude(2.0)
-        >> up(1.0)
+/// Every `.svg` in `dir` as an emblem named after the file, and every line of every `.txt` as
+/// a set of initials
+fn emblems(dir: &Path) -> Vec<(String, Emblem)> {
+    let display = dir.display();
+
+    let mut paths = fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("could not read {display}: {err}"))
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+
+    // keep the export order independent of directory iteration order
+    paths.sort();
+
+    let mut out = vec![];
+
+    for path in paths {
+        let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("svg") => {
+                out.push((stem.to_string(), Emblem::Svg(path.clone())));
+            }
+            Some(ext) if ext.eq_ignore_ascii_case("txt") => {
+                let list = fs::read_to_string(&path)
+                    .unwrap_or_else(|err| panic!("could not read {}: {err}", path.display()));
+                for initials in list.lines().map(str::trim).filter(|line| !line.is_empty()) {
+                    out.push((
+                        format!("initials_{initials}"),
+                        Emblem::Initials(initials.to_string()),
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    assert!(
+        !out.is_empty(),
+        "{display} has no .svg emblems or .txt initials"
+    );
+
+    out
 }
 
 fn main() {
-    let main = band().bg() + clip();
-    let star = main.clone() + star();
-    let heart = main.clone() + heart();
+    let dir = std::env::var(EMBLEMS_ENV)
+        .ok()
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| EMBLEMS_DIR.to_string());
 
-    let settings = fragment_count(150).preview(25);
+    let mut keepers = emblems(Path::new(&dir))
+        .into_iter()
+        .map(|(name, emblem)| Keeper {
+            name,
+            emblem: Some(emblem),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
 
-    let star = settings.apply(&star);
-    let heart = settings.apply(&heart);
+    keepers.extend([
+        Keeper::default(),
+        Keeper {
+            name: "backpack_keeper".to_string(),
+            strap: BACKPACK_WEBBING,
+            style: Style::SnapOpen,
+            wall: 2.0,
+            ..Default::default()
+        },
+        Keeper {
+            name: "watch_keeper".to_string(),
+            strap: WATCH_BAND,
+            straps: 2,
+            wall: 1.2,
+            ..Default::default()
+        },
+        Keeper {
+            name: "lanyard_keeper".to_string(),
+            strap: LANYARD,
+            style: Style::SnapOpen,
+            ..Default::default()
+        },
+    ]);
+
+    let settings = fragment_count(150).preview(25);
 
     let targets = &["amf"];
 
-    rsolid::export!(star, "star", targets);
-    rsolid::export!(heart, "heart", targets);
+    for keeper in &keepers {
+        let out = settings.apply(&keeper.model());
+        rsolid::export!(out, &keeper.name, targets);
+    }
 }
@@ -51,7 +51,12 @@ impl Config {
 
         branch -= magnet();
 
-        branch
+        // the seed goes on the underside of the magnet boss
+        common::label(format!("{seed}"))
+            .size(2.0)
+            .margin(0.5)
+            .underside(-HEIGHT * 0.5, common::Region::circle(MAGNET_WIDTH * 0.7))
+            .apply(branch)
     }
 
     fn branch(&self, rng: &mut Rng, len: f64, depth: usize) -> Object<2> {
@@ -85,7 +85,7 @@ fn bracket() -> Object {
     cube([256.0, RACK_THICKNESS, RACK_H]).center(true).into()
 }
 
-fn clip() -> Object {
+fn clip_profile() -> Object<2> {
     let s = square([RACK_H + CLIP_THICKNESS, CLIP_THICKNESS]) >> left(RACK_H * 0.5);
     let top = square([CLIP_THICKNESS, CLIP_W]) >> right(RACK_H * 0.5);
     let bottom = &top >> mirror([1, 0, 0]);
@@ -99,9 +99,11 @@ fn clip() -> Object {
     let mink_v = circle(0.5);
 
     let shape = (s + top + bottom + clip + ball).minkowski(mink_v);
-    let shape = shape >> scale([1.03, 1.0, 1.0]);
+    shape >> scale([1.03, 1.0, 1.0])
+}
 
-    shape
+fn clip() -> Object {
+    clip_profile()
         >> linear_extrude(BASE_OR * 2.0 + CLIP_THICKNESS).center(true)
         >> rotate_y(-90)
         >> mirror([0, 1, 0])
@@ -116,4 +118,7 @@ fn main() {
     let out = h >> fragment_count(200).preview(50);
 
     rsolid::export!(out, &["3mf"]);
+
+    let profile = clip_profile() >> fragment_count(200).preview(50);
+    rsolid::export!(profile, "clip_profile", &["dxf"]);
 }